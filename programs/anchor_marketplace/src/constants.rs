@@ -2,3 +2,7 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+/// Decimals of the USD amounts listing prices are quoted in
+#[constant]
+pub const USD_DECIMALS: u8 = 6;
@@ -0,0 +1,215 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::BaseAssetV1,
+    fetch_plugin, fetch_plugins,
+    instructions::{
+        AddPluginV1CpiBuilder, ApprovePluginAuthorityV1CpiBuilder,
+        RevokePluginAuthorityV1CpiBuilder, TransferV1CpiBuilder, UpdatePluginV1CpiBuilder,
+    },
+    types::{BurnDelegate, FreezeDelegate, Plugin, PluginAuthority, PluginType},
+};
+
+use crate::error::MarketplaceError;
+
+/// Whether the asset carries a frozen freeze delegate
+pub fn is_frozen(asset: &AccountInfo) -> bool {
+    matches!(
+        fetch_plugin::<BaseAssetV1, FreezeDelegate>(asset, PluginType::FreezeDelegate),
+        Ok((_, FreezeDelegate { frozen: true }, _))
+    )
+}
+
+/// Thaws an asset left frozen under the marketplace by a previous sale so its owner can sell it on.
+/// `redemption` is the asset's redemption PDA: while it exists the asset is frozen for redemption
+/// and must not be thawed.
+#[allow(clippy::too_many_arguments)]
+pub fn thaw_sold_asset<'info>(
+    mpl_core_program: &AccountInfo<'info>,
+    asset: &AccountInfo<'info>,
+    collection: Option<&AccountInfo<'info>>,
+    redemption: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    marketplace: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    marketplace_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if !is_frozen(asset) {
+        return Ok(());
+    }
+    require!(
+        redemption.data_is_empty(),
+        MarketplaceError::RedemptionPending
+    );
+
+    UpdatePluginV1CpiBuilder::new(mpl_core_program)
+        .asset(asset)
+        .collection(collection)
+        .payer(payer)
+        .authority(Some(marketplace))
+        .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+        .system_program(system_program)
+        .invoke_signed(marketplace_seeds)?;
+
+    Ok(())
+}
+
+/// Whether the asset carries a plugin of `plugin_type`
+pub fn has_plugin(asset: &AccountInfo, plugin_type: &PluginType) -> Result<bool> {
+    Ok(fetch_plugins(&asset.try_borrow_data()?)
+        .map(|records| {
+            records
+                .iter()
+                .any(|record| record.plugin_type == *plugin_type)
+        })
+        .unwrap_or(false))
+}
+
+/// Adds `plugin` to the asset unless a previous owner already did, then approves `new_authority` over it.
+/// `owner` must own the asset, signing through `signer_seeds` when it is a PDA.
+#[allow(clippy::too_many_arguments)]
+pub fn delegate_plugin<'info>(
+    mpl_core_program: &AccountInfo<'info>,
+    asset: &AccountInfo<'info>,
+    collection: Option<&AccountInfo<'info>>,
+    owner: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    plugin: Plugin,
+    new_authority: Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let plugin_type = PluginType::from(&plugin);
+
+    if !has_plugin(asset, &plugin_type)? {
+        AddPluginV1CpiBuilder::new(mpl_core_program)
+            .asset(asset)
+            .collection(collection)
+            .payer(payer)
+            .authority(Some(owner))
+            .plugin(plugin)
+            .system_program(system_program)
+            .invoke_signed(signer_seeds)?;
+    }
+
+    ApprovePluginAuthorityV1CpiBuilder::new(mpl_core_program)
+        .asset(asset)
+        .collection(collection)
+        .payer(payer)
+        .authority(Some(owner))
+        .plugin_type(plugin_type)
+        .new_authority(PluginAuthority::Address {
+            address: new_authority,
+        })
+        .system_program(system_program)
+        .invoke_signed(signer_seeds)?;
+
+    Ok(())
+}
+
+/// Delivers a sold asset from `owner`, a listing escrow or the holder, to `buyer`. The freeze and burn
+/// delegates are handed to the marketplace while `owner` still owns the asset, so custody and redemption
+/// outlive the listing closed by the sale, and the asset stays frozen under the marketplace until
+/// the buyer relists or redeems it. `owner` signs through `owner_seeds` when it is a PDA.
+#[allow(clippy::too_many_arguments)]
+pub fn release_sold_asset<'info>(
+    mpl_core_program: &AccountInfo<'info>,
+    asset: &AccountInfo<'info>,
+    collection: Option<&AccountInfo<'info>>,
+    owner: &AccountInfo<'info>,
+    buyer: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    marketplace: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    owner_seeds: &[&[&[u8]]],
+    marketplace_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    for plugin in [
+        Plugin::BurnDelegate(BurnDelegate {}),
+        Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+    ] {
+        delegate_plugin(
+            mpl_core_program,
+            asset,
+            collection,
+            owner,
+            payer,
+            system_program,
+            plugin,
+            marketplace.key(),
+            owner_seeds,
+        )?;
+    }
+
+    // Assets sold straight from a holder's wallet may never have been listed
+    if has_plugin(asset, &PluginType::TransferDelegate)? {
+        RevokePluginAuthorityV1CpiBuilder::new(mpl_core_program)
+            .asset(asset)
+            .collection(collection)
+            .payer(payer)
+            .authority(Some(owner))
+            .plugin_type(PluginType::TransferDelegate)
+            .system_program(system_program)
+            .invoke_signed(owner_seeds)?;
+    }
+
+    // The owner signs the transfer outright rather than through a delegate
+    TransferV1CpiBuilder::new(mpl_core_program)
+        .asset(asset)
+        .collection(collection)
+        .payer(payer)
+        .authority(Some(owner))
+        .new_owner(buyer)
+        .system_program(Some(system_program))
+        .invoke_signed(owner_seeds)?;
+
+    UpdatePluginV1CpiBuilder::new(mpl_core_program)
+        .asset(asset)
+        .collection(collection)
+        .payer(payer)
+        .authority(Some(marketplace))
+        .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: true }))
+        .system_program(system_program)
+        .invoke_signed(marketplace_seeds)?;
+
+    Ok(())
+}
+
+/// Revokes the delegates added in `list_nft` while `escrow` still owns the asset, so escrow can sign
+/// as its owner, then hands the asset back to `seller`
+#[allow(clippy::too_many_arguments)]
+pub fn return_listed_asset<'info>(
+    mpl_core_program: &AccountInfo<'info>,
+    asset: &AccountInfo<'info>,
+    collection: Option<&AccountInfo<'info>>,
+    escrow: &AccountInfo<'info>,
+    seller: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    escrow_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    for plugin_type in [
+        PluginType::TransferDelegate,
+        PluginType::BurnDelegate,
+        PluginType::FreezeDelegate,
+    ] {
+        RevokePluginAuthorityV1CpiBuilder::new(mpl_core_program)
+            .asset(asset)
+            .collection(collection)
+            .payer(payer)
+            .authority(Some(escrow))
+            .plugin_type(plugin_type)
+            .system_program(system_program)
+            .invoke_signed(escrow_seeds)?;
+    }
+
+    TransferV1CpiBuilder::new(mpl_core_program)
+        .asset(asset)
+        .collection(collection)
+        .payer(payer)
+        .authority(Some(escrow))
+        .new_owner(seller)
+        .system_program(Some(system_program))
+        .invoke_signed(escrow_seeds)?;
+
+    Ok(())
+}
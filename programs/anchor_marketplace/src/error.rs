@@ -26,4 +26,122 @@ pub enum MarketplaceError {
     ListingNotActive,
     #[msg("You can't list an Item in this marketplace")]
     UnauthorizedCreator,
+    #[msg("No price feed was provided for a spot priced listing")]
+    PriceFeedMissing,
+    #[msg("Price feed is not the one configured for this metal")]
+    PriceFeedMismatch,
+    #[msg("Account is not a valid price feed")]
+    InvalidPriceFeed,
+    #[msg("Price feed is not trading or has a non-positive price")]
+    InvalidPrice,
+    #[msg("Price feed has not been updated recently enough")]
+    StalePrice,
+    #[msg("Price feed confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Confidence basis points cannot exceed 10000 (100%)")]
+    InvalidConfidenceBps,
+    #[msg("Premium basis points cannot exceed 10000 (100%)")]
+    InvalidPremiumBps,
+    #[msg("Weight must be greater than zero")]
+    InvalidWeight,
+    #[msg("Purity must be between 0 and 999.9 fine")]
+    InvalidPurity,
+    #[msg("Only the marketplace admin can perform this action")]
+    UnauthorizedAdmin,
+    #[msg("Payment mint is not accepted by this marketplace")]
+    PaymentMintDisabled,
+    #[msg("Payment mint does not match the accepted payment mint")]
+    PaymentMintMismatch,
+    #[msg("Token account for the payment mint was not provided")]
+    PaymentAccountMissing,
+    #[msg("Vault ID cannot be empty or more than 32 characters long")]
+    InvalidVaultId,
+    #[msg("Serial number cannot be empty or more than 32 characters long")]
+    InvalidSerialNumber,
+    #[msg("Vault does not hold enough unminted metal")]
+    InsufficientVaultInventory,
+    #[msg("Vault holds a different metal than the asset")]
+    VaultMetalMismatch,
+    #[msg("Asset is not backed by this vault")]
+    VaultMismatch,
+    #[msg("Vault inventory cannot drop below minted and reserved metal")]
+    InvalidVaultInventory,
+    #[msg("Asset metal attributes are missing or malformed")]
+    InvalidMetalAttributes,
+    #[msg("Jurisdiction code must be a 2 or 3 letter uppercase country code")]
+    InvalidJurisdictionCode,
+    #[msg("Vault partner cannot custody more than 10 vaults")]
+    TooManyVaults,
+    #[msg("Vault partner is suspended")]
+    VaultPartnerSuspended,
+    #[msg("Vault is not custodied by this vault partner")]
+    VaultNotCustodied,
+    #[msg("Redemption is not in the expected state")]
+    InvalidRedemptionStatus,
+    #[msg("Encryption key cannot be empty")]
+    InvalidEncryptionKey,
+    #[msg("Encrypted shipping details cannot be empty or more than 512 bytes")]
+    InvalidShippingDetails,
+    #[msg("Treasury does not hold enough funds for this withdrawal")]
+    InsufficientTreasuryBalance,
+    #[msg("Signer is not the proposed marketplace admin")]
+    NotPendingAdmin,
+    #[msg("Seller is not permitted to list on this marketplace")]
+    UnauthorizedSeller,
+    #[msg("Royalty basis points or creator shares are invalid")]
+    InvalidRoyalties,
+    #[msg("Royalty recipient accounts do not match the royalties plugin")]
+    RoyaltyRecipientMismatch,
+    #[msg("Collection is not registered as tradable on this marketplace")]
+    CollectionNotTradable,
+    #[msg("Collection is not registered on this marketplace")]
+    CollectionNotRegistered,
+    #[msg("Asset metal does not match the collection product line")]
+    CollectionMetalMismatch,
+    #[msg("Metal, weight and vault of an asset back vault inventory and cannot change")]
+    MetalAttributesLocked,
+    #[msg("Auction parameters are invalid")]
+    InvalidAuction,
+    #[msg("Auction listings are sold through bids")]
+    AuctionListing,
+    #[msg("Listing is not an auction")]
+    NotAnAuction,
+    #[msg("Auction is not accepting bids")]
+    AuctionNotActive,
+    #[msg("Auction has not ended")]
+    AuctionNotEnded,
+    #[msg("Bid is below the reserve price or minimum increment")]
+    BidTooLow,
+    #[msg("Auction already has bids")]
+    AuctionHasBids,
+    #[msg("Bid accounts do not match the highest bid")]
+    BidMismatch,
+    #[msg("Listing has not started")]
+    ListingNotStarted,
+    #[msg("Offer amount and quantity must be positive and expiry in the future")]
+    InvalidOffer,
+    #[msg("Offer has expired")]
+    OfferExpired,
+    #[msg("Asset does not meet the offer's metal, purity or weight")]
+    AssetDoesNotMatchOffer,
+    #[msg("Listing has expired")]
+    ListingExpired,
+    #[msg("Listing has not expired")]
+    ListingNotExpired,
+    #[msg("Listing expiry must be in the future and cannot be set on auctions")]
+    InvalidListingExpiry,
+    #[msg("Vault is listed more than once")]
+    DuplicateVault,
+    #[msg("Asset was not minted by this marketplace")]
+    UnknownAssetOrigin,
+    #[msg("Asset is frozen for a pending redemption")]
+    RedemptionPending,
+    #[msg("Marketplace fee and creator royalties exceed the sale price")]
+    FeesExceedPrice,
+    #[msg("Price only applies to fixed price listings")]
+    PriceNotFixed,
+    #[msg("Vault partner still custodies vaults")]
+    VaultPartnerHasVaults,
+    #[msg("Payment account does not belong to the recipient or the payment mint")]
+    PaymentRecipientMismatch,
 }
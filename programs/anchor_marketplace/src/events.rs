@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    ListingKind, MetalAttributes, MetalType, PendingFee, PendingFeeTimelock, SellerPermission,
+};
+
+#[event]
+pub struct ListingUpdated {
+    pub listing: Pubkey,
+    pub asset: Pubkey,
+    pub seller: Pubkey,
+    pub old_price: u64,
+    pub new_price: u64,
+    pub old_token_id: u16,
+    pub new_token_id: u16,
+    pub kind: ListingKind,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionRequested {
+    pub redemption: Pubkey,
+    pub asset: Pubkey,
+    pub owner: Pubkey,
+    pub vault: Pubkey,
+    pub payment_mint: Pubkey,
+    pub fee: u64,
+    pub vault_partner: Pubkey,
+    pub shipping_commitment: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionAccepted {
+    pub redemption: Pubkey,
+    pub asset: Pubkey,
+    pub vault_partner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionRejected {
+    pub redemption: Pubkey,
+    pub asset: Pubkey,
+    pub vault_partner: Pubkey,
+    pub refunded_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionCancelled {
+    pub redemption: Pubkey,
+    pub asset: Pubkey,
+    pub owner: Pubkey,
+    pub refunded_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionShipped {
+    pub redemption: Pubkey,
+    pub asset: Pubkey,
+    pub owner: Pubkey,
+    pub vault_partner: Pubkey,
+    pub weight_mg: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub marketplace: Pubkey,
+    pub destination: Pubkey,
+    /// The wrapped SOL mint for native SOL withdrawals
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketplaceConfigUpdated {
+    pub marketplace: Pubkey,
+    pub name: String,
+    pub fee_bps: u16,
+    pub fee_timelock: u64,
+    pub pending_fee: Option<PendingFee>,
+    pub pending_fee_timelock: Option<PendingFeeTimelock>,
+    pub seller_permission: SellerPermission,
+    pub expiry_bounty: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub marketplace: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminAccepted {
+    pub marketplace: Pubkey,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SellerApprovalUpdated {
+    pub marketplace: Pubkey,
+    pub seller: Pubkey,
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultPartnerRegistered {
+    pub marketplace: Pubkey,
+    pub vault_partner: Pubkey,
+    pub authority: Pubkey,
+    pub name: String,
+    pub jurisdiction_code: String,
+    pub vaults: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultPartnerSuspended {
+    pub marketplace: Pubkey,
+    pub vault_partner: Pubkey,
+    pub suspended: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultPartnerRemoved {
+    pub marketplace: Pubkey,
+    pub vault_partner: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetSold {
+    pub marketplace: Pubkey,
+    pub asset: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub payment_mint: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    /// Total paid out to creators
+    pub royalty: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+/// Fields that were not updated are `None`
+#[event]
+pub struct AssetMetadataUpdated {
+    pub asset: Pubkey,
+    pub authority: Pubkey,
+    pub previous_name: Option<String>,
+    pub name: Option<String>,
+    pub previous_uri: Option<String>,
+    pub uri: Option<String>,
+    pub previous_attributes: Option<MetalAttributes>,
+    pub attributes: Option<MetalAttributes>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidPlaced {
+    pub listing: Pubkey,
+    pub asset: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    /// Bidder refunded by this bid
+    pub outbid: Option<Pubkey>,
+    /// Auction end time after any anti-sniping extension
+    pub end_time: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted alongside `AssetSold` when the auction had a winner
+#[event]
+pub struct AuctionSettled {
+    pub listing: Pubkey,
+    pub asset: Pubkey,
+    pub seller: Pubkey,
+    pub winner: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferMade {
+    pub offer: Pubkey,
+    pub asset: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub payment_mint: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferCancelled {
+    pub offer: Pubkey,
+    pub asset: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionOfferMade {
+    pub offer: Pubkey,
+    pub collection: Pubkey,
+    pub buyer: Pubkey,
+    pub metal: MetalType,
+    pub min_purity: u16,
+    pub min_weight_mg: u64,
+    pub price: u64,
+    pub quantity: u32,
+    pub payment_mint: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionOfferFilled {
+    pub offer: Pubkey,
+    pub asset: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    /// Units still wanted after this fill
+    pub remaining: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionOfferCancelled {
+    pub offer: Pubkey,
+    pub buyer: Pubkey,
+    /// Amount refunded for the units left unfilled
+    pub refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingExpired {
+    pub listing: Pubkey,
+    pub asset: Pubkey,
+    pub seller: Pubkey,
+    pub cranker: Pubkey,
+    /// Lamports paid to `cranker` out of the listing's rent
+    pub bounty: u64,
+    pub timestamp: i64,
+}
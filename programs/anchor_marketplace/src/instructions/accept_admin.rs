@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::{AdminAccepted, Marketplace};
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.pending_admin == Some(new_admin.key()) @ MarketplaceError::NotPendingAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        let old_admin = self.marketplace.admin;
+        self.marketplace.admin = self.new_admin.key();
+        self.marketplace.pending_admin = None;
+
+        emit!(AdminAccepted {
+            marketplace: self.marketplace.key(),
+            old_admin,
+            new_admin: self.new_admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
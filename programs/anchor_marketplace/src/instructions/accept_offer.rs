@@ -0,0 +1,373 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    types::UpdateAuthority,
+};
+
+use crate::delegate::{release_sold_asset, thaw_sold_asset};
+use crate::error::MarketplaceError;
+use crate::payment::{
+    close_escrow_token_account, pay_sale, payment_account, PaymentSource, SaleSplit,
+};
+use crate::{
+    AssetSold, CollectionConfig, Listing, Marketplace, Offer, PaymentMint, SaleRecord,
+    SellerProfile,
+};
+
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    /// The asset owner, or its seller while it is listed
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        address = offer.buyer,
+    )]
+    pub buyer: SystemAccount<'info>,
+
+    /// The MPL Core asset being sold
+    #[account(
+        mut,
+        constraint = asset.key() == offer.asset @ MarketplaceError::AssetMismatch,
+        constraint = match asset.update_authority {
+            // assets minted before the marketplace PDA became update authority are held by the admin
+            UpdateAuthority::Address(authority) => authority == marketplace.key() || authority == marketplace.admin,
+            UpdateAuthority::Collection(_) => collection.is_some(),
+            UpdateAuthority::None => false,
+        } @ MarketplaceError::NotUpdateAuthority,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    /// The collection that this asset belongs to
+    #[account(
+        mut,
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()) @ MarketplaceError::CollectionMismatch,
+    )]
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
+
+    /// Registration of `collection`, which must be tradable for its assets to be sold
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection_config.collection.as_ref()],
+        bump = collection_config.bump,
+    )]
+    pub collection_config: Option<Account<'info, CollectionConfig>>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.can_list(&seller.key(), &seller_profile) @ MarketplaceError::UnauthorizedSeller,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        seeds = [b"seller_profile", marketplace.key().as_ref(), seller.key().as_ref()],
+        bump,
+        space = 8 + SellerProfile::INIT_SPACE
+    )]
+    pub seller_profile: Account<'info, SellerProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"offer", marketplace.key().as_ref(), asset.key().as_ref(), buyer.key().as_ref()],
+        bump = offer.bump,
+        close = buyer,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// The asset's listing, required while it is listed and closed by the sale
+    #[account(
+        mut,
+        seeds = [b"listing", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump = listing.bump,
+        close = seller,
+        constraint = seller.key() == listing.seller @ MarketplaceError::SellerMismatch,
+        constraint = listing.highest_bidder().is_none() @ MarketplaceError::AuctionHasBids,
+    )]
+    pub listing: Option<Account<'info, Listing>>,
+
+    /// CHECK: The escrow account holding the asset while it is listed, checked against `listing` on use
+    #[account(mut)]
+    pub escrow: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        seeds = [b"sale_record", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump,
+        space = 8 + SaleRecord::INIT_SPACE
+    )]
+    pub sale_record: Account<'info, SaleRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    /// The accepted mint the offer was made in
+    #[account(
+        seeds = [b"payment_mint", marketplace.key().as_ref(), payment_mint_config.mint.as_ref()],
+        bump = payment_mint_config.bump,
+        constraint = payment_mint_config.mint == offer.payment_mint @ MarketplaceError::PaymentMintMismatch,
+    )]
+    pub payment_mint_config: Account<'info, PaymentMint>,
+
+    /// CHECK: Feed converting the payment mint into USD for the sale record, validated against `payment_mint_config`
+    pub payment_price_feed: Option<UncheckedAccount<'info>>,
+
+    /// The SPL mint of the offer, omitted for SOL offers
+    #[account(
+        address = offer.payment_mint @ MarketplaceError::PaymentMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = offer,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = payment_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The asset's redemption, which must not exist for a frozen asset to be sold on
+    #[account(
+        seeds = [b"redemption", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump,
+    )]
+    pub redemption: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> AcceptOffer<'info> {
+    /// Creator wallets, or their payment token accounts for SPL offers, are passed as
+    /// `creator_accounts` in the order of the royalties plugin
+    pub fn accept_offer(
+        &mut self,
+        creator_accounts: &[AccountInfo<'info>],
+        bumps: &AcceptOfferBumps,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(
+            !self.offer.is_expired(clock.unix_timestamp),
+            MarketplaceError::OfferExpired
+        );
+        require!(
+            self.payment_mint.is_some() != self.offer.is_native_payment(),
+            MarketplaceError::PaymentMintMismatch
+        );
+        if let Some(collection) = &self.collection {
+            require!(
+                self.collection_config.as_ref().is_some_and(|config| {
+                    config.collection == collection.key() && config.tradable
+                }),
+                MarketplaceError::CollectionNotTradable
+            );
+        }
+
+        if self.seller_profile.seller == Pubkey::default() {
+            self.seller_profile.set_inner(SellerProfile {
+                marketplace: self.marketplace.key(),
+                seller: self.seller.key(),
+                approved: false,
+                listing_count: 0,
+                sale_count: 0,
+                volume: 0,
+                bump: bumps.seller_profile,
+            });
+        }
+
+        let split = self.pay_seller(creator_accounts)?;
+        self.deliver_asset()?;
+
+        let payment_price_feed = self
+            .payment_price_feed
+            .as_ref()
+            .map(|feed| feed.to_account_info());
+        let usd_price = self.payment_mint_config.usd_from_amount(
+            self.offer.amount,
+            &self.marketplace.oracle,
+            payment_price_feed.as_ref(),
+        )?;
+
+        self.seller_profile.record_sale(usd_price)?;
+        self.sale_record.record(
+            AssetSold {
+                marketplace: self.marketplace.key(),
+                asset: self.asset.key(),
+                seller: self.seller.key(),
+                buyer: self.buyer.key(),
+                price: usd_price,
+                payment_mint: self.offer.payment_mint,
+                amount: self.offer.amount,
+                fee: split.fee,
+                royalty: split.royalty,
+                slot: clock.slot,
+                timestamp: clock.unix_timestamp,
+            },
+            bumps.sale_record,
+        )
+    }
+
+    /// Pays the escrowed offer out through the same split as a purchase, the offer account's
+    /// rent is returned by `close = buyer`
+    fn pay_seller(&self, creator_accounts: &[AccountInfo<'info>]) -> Result<SaleSplit> {
+        let marketplace_key = self.marketplace.key();
+        let offer_seeds: &[&[&[u8]]] = &[&[
+            b"offer",
+            marketplace_key.as_ref(),
+            self.offer.asset.as_ref(),
+            self.offer.buyer.as_ref(),
+            &[self.offer.bump],
+        ]];
+
+        let payment_mint = self.payment_mint.as_ref();
+        let source = PaymentSource::Escrow {
+            escrow: self.offer.to_account_info(),
+            token_account: self.escrow_payment_account.as_ref(),
+            token_program: self.token_program.to_account_info(),
+            signer_seeds: offer_seeds,
+        };
+
+        let collection = self.collection.as_ref().map(|c| c.to_account_info());
+        let split = pay_sale(
+            &source,
+            payment_mint,
+            &self.marketplace,
+            &self.asset.to_account_info(),
+            collection.as_ref(),
+            creator_accounts,
+            payment_account(
+                payment_mint,
+                self.treasury.to_account_info(),
+                self.treasury_payment_account.as_ref(),
+            )?,
+            payment_account(
+                payment_mint,
+                self.seller.to_account_info(),
+                self.seller_payment_account.as_ref(),
+            )?,
+            self.offer.amount,
+        )?;
+
+        close_escrow_token_account(
+            self.offer.to_account_info(),
+            self.escrow_payment_account.as_ref(),
+            self.buyer.to_account_info(),
+            self.token_program.to_account_info(),
+            offer_seeds,
+        )?;
+
+        Ok(split)
+    }
+
+    /// Moves the asset to the buyer out of the listing escrow when listed, otherwise straight
+    /// from the seller's wallet
+    fn deliver_asset(&self) -> Result<()> {
+        let mpl_core_program = self.mpl_core_program.to_account_info();
+        let asset = self.asset.to_account_info();
+        let collection = self.collection.as_ref().map(|c| c.to_account_info());
+        let marketplace = self.marketplace.to_account_info();
+        let seller = self.seller.to_account_info();
+        let system_program = self.system_program.to_account_info();
+
+        let marketplace_id = self.marketplace.id.to_le_bytes();
+        let marketplace_seeds: &[&[&[u8]]] = &[&[
+            b"marketplace",
+            marketplace_id.as_ref(),
+            &[self.marketplace.bump],
+        ]];
+
+        match (&self.listing, &self.escrow) {
+            (Some(listing), Some(escrow)) => {
+                let listing_key = listing.key();
+                let escrow_seeds: &[&[&[u8]]] =
+                    &[&[b"escrow", listing_key.as_ref(), &[listing.escrow_bump]]];
+
+                let escrow_key = Pubkey::create_program_address(escrow_seeds[0], &crate::ID)
+                    .map_err(|_| error!(MarketplaceError::AssetNotInEscrow))?;
+                require!(
+                    escrow.key() == escrow_key && self.asset.owner == escrow_key,
+                    MarketplaceError::AssetNotInEscrow
+                );
+
+                release_sold_asset(
+                    &mpl_core_program,
+                    &asset,
+                    collection.as_ref(),
+                    &escrow.to_account_info(),
+                    &self.buyer.to_account_info(),
+                    &seller,
+                    &marketplace,
+                    &system_program,
+                    escrow_seeds,
+                    marketplace_seeds,
+                )
+            }
+            (None, None) => {
+                require_keys_eq!(
+                    self.asset.owner,
+                    self.seller.key(),
+                    MarketplaceError::NotAssetOwner
+                );
+
+                // An asset bought on the marketplace stays frozen under the marketplace until it is sold on
+                thaw_sold_asset(
+                    &mpl_core_program,
+                    &asset,
+                    collection.as_ref(),
+                    &self.redemption.to_account_info(),
+                    &seller,
+                    &marketplace,
+                    &system_program,
+                    marketplace_seeds,
+                )?;
+
+                release_sold_asset(
+                    &mpl_core_program,
+                    &asset,
+                    collection.as_ref(),
+                    &seller,
+                    &self.buyer.to_account_info(),
+                    &seller,
+                    &marketplace,
+                    &system_program,
+                    &[],
+                    marketplace_seeds,
+                )
+            }
+            _ => err!(MarketplaceError::AssetNotInEscrow),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    Marketplace, MarketplaceError, Redemption, RedemptionAccepted, RedemptionStatus, VaultPartner,
+};
+
+#[derive(Accounts)]
+pub struct AcceptRedemption<'info> {
+    pub vault_partner_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_partner", marketplace.key().as_ref(), vault_partner_authority.key().as_ref()],
+        bump = vault_partner.bump,
+        constraint = vault_partner.is_active() @ MarketplaceError::VaultPartnerSuspended,
+        constraint = vault_partner.custodies(&redemption.vault) @ MarketplaceError::VaultNotCustodied,
+    )]
+    pub vault_partner: Account<'info, VaultPartner>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"redemption", marketplace.key().as_ref(), redemption.asset.as_ref()],
+        bump = redemption.bump,
+        constraint = redemption.status == RedemptionStatus::Requested @ MarketplaceError::InvalidRedemptionStatus,
+    )]
+    pub redemption: Account<'info, Redemption>,
+}
+
+impl<'info> AcceptRedemption<'info> {
+    pub fn accept_redemption(&mut self) -> Result<()> {
+        self.redemption.status = RedemptionStatus::Accepted;
+
+        emit!(RedemptionAccepted {
+            redemption: self.redemption.key(),
+            asset: self.redemption.asset,
+            vault_partner: self.vault_partner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::error::MarketplaceError;
+use crate::{Marketplace, PaymentMint};
+
+#[derive(Accounts)]
+pub struct AddPaymentMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"payment_mint", marketplace.key().as_ref(), mint.key().as_ref()],
+        bump,
+        space = 8 + PaymentMint::INIT_SPACE
+    )]
+    pub payment_mint: Account<'info, PaymentMint>,
+
+    /// Treasury token account fees in this mint are paid into
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_payment_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> AddPaymentMint<'info> {
+    pub fn add_payment_mint(
+        &mut self,
+        params: PaymentMintParams,
+        bumps: &AddPaymentMintBumps,
+    ) -> Result<()> {
+        self.payment_mint.set_inner(PaymentMint {
+            marketplace: self.marketplace.key(),
+            mint: self.mint.key(),
+            decimals: self.mint.decimals,
+            enabled: params.enabled,
+            price_feed: params.price_feed,
+            bump: bumps.payment_mint,
+        });
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct PaymentMintParams {
+    pub enabled: bool,
+    pub price_feed: Option<Pubkey>,
+}
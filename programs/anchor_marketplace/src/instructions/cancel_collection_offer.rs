@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::MarketplaceError;
+use crate::payment::{close_escrow_token_account, payment_account, release_escrowed_payment};
+use crate::{CollectionOffer, CollectionOfferCancelled, Marketplace};
+
+#[derive(Accounts)]
+pub struct CancelCollectionOffer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"collection_offer", marketplace.key().as_ref(), buyer.key().as_ref(), collection_offer.id.to_le_bytes().as_ref()],
+        bump = collection_offer.bump,
+        close = buyer,
+    )]
+    pub collection_offer: Account<'info, CollectionOffer>,
+
+    #[account(
+        address = collection_offer.payment_mint @ MarketplaceError::PaymentMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = collection_offer,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelCollectionOffer<'info> {
+    /// Refunds the units left unfilled, the offer account's rent is returned by `close = buyer`
+    pub fn cancel_collection_offer(&mut self) -> Result<()> {
+        require!(
+            self.payment_mint.is_some() != self.collection_offer.is_native_payment(),
+            MarketplaceError::PaymentMintMismatch
+        );
+
+        let marketplace_key = self.marketplace.key();
+        let offer_id = self.collection_offer.id.to_le_bytes();
+        let offer_seeds: &[&[&[u8]]] = &[&[
+            b"collection_offer",
+            marketplace_key.as_ref(),
+            self.collection_offer.buyer.as_ref(),
+            offer_id.as_ref(),
+            &[self.collection_offer.bump],
+        ]];
+
+        let refunded = self.collection_offer.escrowed_amount()?;
+        let payment_mint = self.payment_mint.as_ref();
+        release_escrowed_payment(
+            payment_mint,
+            self.collection_offer.to_account_info(),
+            self.escrow_payment_account.as_ref(),
+            payment_account(
+                payment_mint,
+                self.buyer.to_account_info(),
+                self.buyer_payment_account.as_ref(),
+            )?,
+            self.token_program.to_account_info(),
+            refunded,
+            offer_seeds,
+        )?;
+        close_escrow_token_account(
+            self.collection_offer.to_account_info(),
+            self.escrow_payment_account.as_ref(),
+            self.buyer.to_account_info(),
+            self.token_program.to_account_info(),
+            offer_seeds,
+        )?;
+
+        emit!(CollectionOfferCancelled {
+            offer: self.collection_offer.key(),
+            buyer: self.buyer.key(),
+            refunded,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    types::UpdateAuthority,
+};
+
+use crate::delegate::return_listed_asset;
+use crate::error::MarketplaceError;
+use crate::{Listing, Marketplace};
+
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// The MPL Core asset being delisted
+    #[account(
+        mut,
+        constraint = asset.key() == listing.mint @ MarketplaceError::AssetMismatch,
+        constraint = matches!(asset.update_authority, UpdateAuthority::Collection(_)) == collection.is_some() @ MarketplaceError::CollectionMismatch,
+        constraint = asset.owner == escrow.key() @ MarketplaceError::AssetNotInEscrow,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    /// The collection that this asset belongs to
+    #[account(
+        mut,
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()) @ MarketplaceError::CollectionMismatch,
+    )]
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"listing", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump = listing.bump,
+        close = seller,
+        constraint = listing.is_active @ MarketplaceError::ListingNotActive,
+        constraint = seller.key() == listing.seller @ MarketplaceError::SellerMismatch,
+        constraint = listing.highest_bidder().is_none() @ MarketplaceError::AuctionHasBids,
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// CHECK: The escrow account that currently holds the asset
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = listing.escrow_bump,
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelListing<'info> {
+    pub fn cancel_listing(&mut self) -> Result<()> {
+        let listing_key = self.listing.key();
+        let escrow_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            listing_key.as_ref(),
+            &[self.listing.escrow_bump],
+        ]];
+
+        let collection_info = self.collection.as_ref().map(|c| c.to_account_info());
+
+        return_listed_asset(
+            &self.mpl_core_program.to_account_info(),
+            &self.asset.to_account_info(),
+            collection_info.as_ref(),
+            &self.escrow.to_account_info(),
+            &self.seller.to_account_info(),
+            &self.seller.to_account_info(),
+            &self.system_program.to_account_info(),
+            escrow_seeds,
+        )
+    }
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::MarketplaceError;
+use crate::payment::{close_escrow_token_account, payment_account, release_escrowed_payment};
+use crate::{Marketplace, Offer, OfferCancelled};
+
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"offer", marketplace.key().as_ref(), offer.asset.as_ref(), buyer.key().as_ref()],
+        bump = offer.bump,
+        close = buyer,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(
+        address = offer.payment_mint @ MarketplaceError::PaymentMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = offer,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelOffer<'info> {
+    /// Refunds the escrowed offer, the offer account's rent is returned by `close = buyer`
+    pub fn cancel_offer(&mut self) -> Result<()> {
+        require!(
+            self.payment_mint.is_some() != self.offer.is_native_payment(),
+            MarketplaceError::PaymentMintMismatch
+        );
+
+        let marketplace_key = self.marketplace.key();
+        let offer_seeds: &[&[&[u8]]] = &[&[
+            b"offer",
+            marketplace_key.as_ref(),
+            self.offer.asset.as_ref(),
+            self.offer.buyer.as_ref(),
+            &[self.offer.bump],
+        ]];
+
+        let payment_mint = self.payment_mint.as_ref();
+        release_escrowed_payment(
+            payment_mint,
+            self.offer.to_account_info(),
+            self.escrow_payment_account.as_ref(),
+            payment_account(
+                payment_mint,
+                self.buyer.to_account_info(),
+                self.buyer_payment_account.as_ref(),
+            )?,
+            self.token_program.to_account_info(),
+            self.offer.amount,
+            offer_seeds,
+        )?;
+        close_escrow_token_account(
+            self.offer.to_account_info(),
+            self.escrow_payment_account.as_ref(),
+            self.buyer.to_account_info(),
+            self.token_program.to_account_info(),
+            offer_seeds,
+        )?;
+
+        emit!(OfferCancelled {
+            offer: self.offer.key(),
+            asset: self.offer.asset,
+            buyer: self.buyer.key(),
+            amount: self.offer.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
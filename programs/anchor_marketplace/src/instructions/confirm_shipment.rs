@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    instructions::{BurnV1CpiBuilder, UpdatePluginV1CpiBuilder},
+    types::{FreezeDelegate, Plugin, UpdateAuthority},
+};
+
+use crate::payment::{close_escrow_token_account, payment_account, release_escrowed_payment};
+use crate::{
+    Marketplace, MarketplaceError, MetalAttributes, Redemption, RedemptionShipped,
+    RedemptionStatus, Vault, VaultPartner,
+};
+
+#[derive(Accounts)]
+pub struct ConfirmShipment<'info> {
+    #[account(mut)]
+    pub vault_partner_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_partner", marketplace.key().as_ref(), vault_partner_authority.key().as_ref()],
+        bump = vault_partner.bump,
+        constraint = vault_partner.is_active() @ MarketplaceError::VaultPartnerSuspended,
+        constraint = vault_partner.custodies(&redemption.vault) @ MarketplaceError::VaultNotCustodied,
+    )]
+    pub vault_partner: Account<'info, VaultPartner>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"redemption", marketplace.key().as_ref(), redemption.asset.as_ref()],
+        bump = redemption.bump,
+        close = owner,
+        constraint = redemption.status == RedemptionStatus::Accepted @ MarketplaceError::InvalidRedemptionStatus,
+    )]
+    pub redemption: Account<'info, Redemption>,
+
+    /// The holder who requested the redemption
+    #[account(
+        mut,
+        address = redemption.owner,
+    )]
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        address = redemption.asset @ MarketplaceError::AssetMismatch,
+        constraint = matches!(asset.update_authority, UpdateAuthority::Collection(_)) == collection.is_some() @ MarketplaceError::CollectionMismatch,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    /// The collection the asset belongs to, required by MPL Core for collection assets
+    #[account(
+        mut,
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()) @ MarketplaceError::CollectionMismatch,
+    )]
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
+
+    /// The vault releasing the bar backing this asset
+    #[account(
+        mut,
+        address = redemption.vault @ MarketplaceError::VaultMismatch,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        address = redemption.payment_mint @ MarketplaceError::PaymentMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = redemption,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: MPL Program ID
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ConfirmShipment<'info> {
+    pub fn confirm_shipment(&mut self) -> Result<()> {
+        require!(
+            self.payment_mint.is_some() != self.redemption.is_native_payment(),
+            MarketplaceError::PaymentMintMismatch
+        );
+
+        // Release the backing bar from vault inventory
+        let attributes = MetalAttributes::from_asset(&self.asset.to_account_info())?;
+        require!(
+            attributes.vault_id == self.vault.vault_id,
+            MarketplaceError::VaultMismatch
+        );
+        self.vault.credit_redeemed(attributes.weight_mg)?;
+
+        let marketplace_key = self.marketplace.key();
+        let asset_key = self.asset.key();
+        let redemption_seeds: &[&[&[u8]]] = &[&[
+            b"redemption",
+            marketplace_key.as_ref(),
+            asset_key.as_ref(),
+            &[self.redemption.bump],
+        ]];
+
+        // Pay the escrowed fee to treasury
+        let payment_mint = self.payment_mint.as_ref();
+        release_escrowed_payment(
+            payment_mint,
+            self.redemption.to_account_info(),
+            self.escrow_payment_account.as_ref(),
+            payment_account(
+                payment_mint,
+                self.treasury.to_account_info(),
+                self.treasury_payment_account.as_ref(),
+            )?,
+            self.token_program.to_account_info(),
+            self.redemption.fee,
+            redemption_seeds,
+        )?;
+        close_escrow_token_account(
+            self.redemption.to_account_info(),
+            self.escrow_payment_account.as_ref(),
+            self.owner.to_account_info(),
+            self.token_program.to_account_info(),
+            redemption_seeds,
+        )?;
+
+        let marketplace_id = self.marketplace.id.to_le_bytes();
+        let marketplace_seeds: &[&[&[u8]]] = &[&[
+            b"marketplace",
+            marketplace_id.as_ref(),
+            &[self.marketplace.bump],
+        ]];
+        let collection = self.collection.as_ref().map(|c| c.to_account_info());
+
+        // Thaw and burn the asset through the freeze and burn delegates the marketplace holds
+        UpdatePluginV1CpiBuilder::new(&self.mpl_core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(collection.as_ref())
+            .payer(&self.vault_partner_authority.to_account_info())
+            .authority(Some(&self.marketplace.to_account_info()))
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+            .system_program(&self.system_program.to_account_info())
+            .invoke_signed(marketplace_seeds)?;
+
+        BurnV1CpiBuilder::new(&self.mpl_core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(collection.as_ref())
+            .payer(&self.vault_partner_authority.to_account_info())
+            .authority(Some(&self.marketplace.to_account_info()))
+            .system_program(Some(&self.system_program.to_account_info()))
+            .invoke_signed(marketplace_seeds)?;
+
+        emit!(RedemptionShipped {
+            redemption: self.redemption.key(),
+            asset: asset_key,
+            owner: self.owner.key(),
+            vault_partner: self.vault_partner.key(),
+            weight_mg: attributes.weight_mg,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
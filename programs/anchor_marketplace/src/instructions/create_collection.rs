@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use mpl_core::instructions::CreateCollectionV1CpiBuilder;
+
+use crate::error::MarketplaceError;
+use crate::{CollectionConfig, Marketplace, MetalType};
+
+#[derive(Accounts)]
+pub struct CreateCollection<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: This account will be created by MPL Core
+    #[account(mut, signer)]
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"collection", marketplace.key().as_ref(), collection.key().as_ref()],
+        bump,
+        space = 8 + CollectionConfig::INIT_SPACE
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// CHECK: MPL Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateCollection<'info> {
+    pub fn create_collection(
+        &mut self,
+        params: CreateCollectionParams,
+        bumps: &CreateCollectionBumps,
+    ) -> Result<()> {
+        require!(!params.name.is_empty(), MarketplaceError::UndefinedName);
+
+        // The marketplace PDA is the update authority so admin rotation keeps control of the collection
+        CreateCollectionV1CpiBuilder::new(&self.mpl_core_program.to_account_info())
+            .collection(&self.collection.to_account_info())
+            .update_authority(Some(&self.marketplace.to_account_info()))
+            .payer(&self.admin.to_account_info())
+            .system_program(&self.system_program.to_account_info())
+            .name(params.name)
+            .uri(params.uri)
+            .invoke()?;
+
+        self.collection_config.set_inner(CollectionConfig {
+            marketplace: self.marketplace.key(),
+            collection: self.collection.key(),
+            metal: params.metal,
+            tradable: params.tradable,
+            bump: bumps.collection_config,
+        });
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct CreateCollectionParams {
+    pub name: String,
+    pub uri: String,
+    pub metal: MetalType,
+    pub tradable: bool,
+}
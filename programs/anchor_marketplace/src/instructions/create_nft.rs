@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
-use mpl_core::{accounts::BaseCollectionV1, instructions::CreateV1CpiBuilder};
+use mpl_core::{
+    accounts::BaseCollectionV1,
+    instructions::CreateV1CpiBuilder,
+    types::{Plugin, PluginAuthority, PluginAuthorityPair},
+};
 
 pub use crate::error::MarketplaceError;
-use crate::Marketplace;
+use crate::{CollectionConfig, Marketplace, MetalAttributes, RoyaltyConfig, Vault, VaultPartner};
 
 #[derive(Accounts)]
 #[instruction(params: CreateNFTParams)]
@@ -25,18 +29,47 @@ pub struct CreateNFT<'info> {
     //  pub asset: UncheckedAccount<'info>,
     /// The collection that this asset will belong to
     #[account(
-        constraint = collection.update_authority == creator.key() @ MarketplaceError::NotUpdateAuthority,
+        mut,
+        constraint = collection.update_authority == marketplace.key() @ MarketplaceError::NotUpdateAuthority,
     )]
     pub collection: Option<Account<'info, BaseCollectionV1>>,
 
+    /// Registration of `collection`, required when minting into a collection
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection_config.collection.as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.metal == params.attributes.metal @ MarketplaceError::CollectionMetalMismatch,
+    )]
+    pub collection_config: Option<Account<'info, CollectionConfig>>,
+
     #[account(
         mut,
-        seeds = [b"marketplace", creator.key().as_ref()],
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
         bump = marketplace.bump,
         constraint = marketplace.admin == creator.key() @ MarketplaceError::UnauthorizedCreator
     )]
     pub marketplace: Account<'info, Marketplace>,
 
+    /// The vault holding the bar this asset is backed by
+    #[account(
+        mut,
+        seeds = [b"vault", marketplace.key().as_ref(), params.attributes.vault_id.as_bytes()],
+        bump = vault.bump,
+        constraint = vault.metal == params.attributes.metal @ MarketplaceError::VaultMetalMismatch,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// The vault partner custodying `vault`, attesting to the inventory change
+    pub vault_partner_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_partner", marketplace.key().as_ref(), vault_partner_authority.key().as_ref()],
+        bump = vault_partner.bump,
+        constraint = vault_partner.is_active() @ MarketplaceError::VaultPartnerSuspended,
+        constraint = vault_partner.custodies(&vault.key()) @ MarketplaceError::VaultNotCustodied,
+    )]
+    pub vault_partner: Account<'info, VaultPartner>,
+
     /// CHECK: MPL Core program
     #[account(address = mpl_core::ID)]
     pub mpl_core_program: UncheckedAccount<'info>,
@@ -45,12 +78,42 @@ pub struct CreateNFT<'info> {
 
 impl<'info> CreateNFT<'info> {
     pub fn create_nft(&mut self, params: CreateNFTParams) -> Result<()> {
+        params.attributes.validate()?;
+        if let Some(royalties) = &params.royalties {
+            royalties.validate(self.marketplace.fee_bps_at(Clock::get()?.unix_timestamp))?;
+        }
+        if let Some(collection) = &self.collection {
+            require!(
+                self.collection_config
+                    .as_ref()
+                    .is_some_and(|config| config.collection == collection.key()),
+                MarketplaceError::CollectionNotRegistered
+            );
+        }
+        self.vault.debit_minted(params.attributes.weight_mg)?;
+
         // Store AccountInfo values in variables to extend their lifetime
         let mpl_core_program_info = self.mpl_core_program.to_account_info();
         let asset_info = self.asset.to_account_info();
         let creator_info = self.creator.to_account_info();
         let system_program_info = self.system_program.to_account_info();
         let collection_info = self.collection.as_ref().map(|c| c.to_account_info());
+        let marketplace_info = self.marketplace.to_account_info();
+
+        // Metal attributes are managed by the marketplace so holders cannot tamper with them
+        let mut plugins = vec![PluginAuthorityPair {
+            plugin: Plugin::Attributes(params.attributes.to_attributes()),
+            authority: Some(PluginAuthority::Address {
+                address: self.marketplace.key(),
+            }),
+        }];
+        if let Some(royalties) = &params.royalties {
+            plugins.push(PluginAuthorityPair {
+                plugin: royalties.to_plugin(),
+                authority: None,
+            });
+        }
+
         // Create the MPL Core asset
         let mut builder = CreateV1CpiBuilder::new(&mpl_core_program_info);
 
@@ -58,13 +121,25 @@ impl<'info> CreateNFT<'info> {
             .asset(&asset_info)
             .payer(&creator_info)
             .owner(Some(&creator_info))
-            .authority(Some(&creator_info))
+            .authority(Some(&marketplace_info))
             .system_program(&system_program_info)
             .name(params.name)
             .collection(collection_info.as_ref())
-            .uri(params.uri);
+            .uri(params.uri)
+            .plugins(plugins);
+
+        // The marketplace PDA is the update authority, directly or through the collection it manages,
+        // so metadata stays manageable across admin rotation
+        if collection_info.is_none() {
+            builder.update_authority(Some(&marketplace_info));
+        }
 
-        builder.invoke()?;
+        let marketplace_id = self.marketplace.id.to_le_bytes();
+        builder.invoke_signed(&[&[
+            b"marketplace",
+            marketplace_id.as_ref(),
+            &[self.marketplace.bump],
+        ]])?;
 
         Ok(())
     }
@@ -74,4 +149,7 @@ impl<'info> CreateNFT<'info> {
 pub struct CreateNFTParams {
     pub name: String,
     pub uri: String,
+    pub attributes: MetalAttributes,
+    /// Royalties paid to creators on every sale through the marketplace
+    pub royalties: Option<RoyaltyConfig>,
 }
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::{Marketplace, MetalType, Vault, VaultPartner, MAX_PARTNER_VAULTS};
+
+#[derive(Accounts)]
+#[instruction(params: CreateVaultParams)]
+pub struct CreateVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"vault", marketplace.key().as_ref(), params.vault_id.as_bytes()],
+        bump,
+        space = 8 + Vault::INIT_SPACE
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// The vault partner taking custody of the vault, attesting to its opening inventory
+    pub vault_partner_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_partner", marketplace.key().as_ref(), vault_partner_authority.key().as_ref()],
+        bump = vault_partner.bump,
+        constraint = vault_partner.is_active() @ MarketplaceError::VaultPartnerSuspended,
+    )]
+    pub vault_partner: Account<'info, VaultPartner>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateVault<'info> {
+    pub fn create_vault(
+        &mut self,
+        params: CreateVaultParams,
+        bumps: &CreateVaultBumps,
+    ) -> Result<()> {
+        require!(
+            !params.vault_id.is_empty() && params.vault_id.len() <= 32,
+            MarketplaceError::InvalidVaultId
+        );
+        require!(
+            self.vault_partner.vaults.len() < MAX_PARTNER_VAULTS,
+            MarketplaceError::TooManyVaults
+        );
+        self.vault_partner.vaults.push(self.vault.key());
+
+        self.vault.set_inner(Vault {
+            marketplace: self.marketplace.key(),
+            metal: params.metal,
+            total_mg: params.total_mg,
+            minted_mg: 0,
+            reserved_mg: 0,
+            bump: bumps.vault,
+            vault_id: params.vault_id,
+        });
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct CreateVaultParams {
+    pub vault_id: String,
+    pub metal: MetalType,
+    pub total_mg: u64,
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    types::UpdateAuthority,
+};
+
+use crate::delegate::return_listed_asset;
+use crate::error::MarketplaceError;
+use crate::{Listing, ListingExpired, Marketplace};
+
+#[derive(Accounts)]
+pub struct ExpireListing<'info> {
+    /// Anyone can return an expired listing, earning the marketplace's expiry bounty
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = seller.key() == listing.seller @ MarketplaceError::SellerMismatch
+    )]
+    pub seller: SystemAccount<'info>,
+
+    /// The MPL Core asset being returned
+    #[account(
+        mut,
+        constraint = asset.key() == listing.mint @ MarketplaceError::AssetMismatch,
+        constraint = matches!(asset.update_authority, UpdateAuthority::Collection(_)) == collection.is_some() @ MarketplaceError::CollectionMismatch,
+        constraint = asset.owner == escrow.key() @ MarketplaceError::AssetNotInEscrow,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    /// The collection that this asset belongs to
+    #[account(
+        mut,
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()) @ MarketplaceError::CollectionMismatch,
+    )]
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"listing", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump = listing.bump,
+        close = seller,
+        constraint = listing.is_active @ MarketplaceError::ListingNotActive,
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// CHECK: The escrow account that currently holds the asset
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = listing.escrow_bump,
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExpireListing<'info> {
+    pub fn expire_listing(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            self.listing.is_expired(now),
+            MarketplaceError::ListingNotExpired
+        );
+
+        let listing_key = self.listing.key();
+        let escrow_seeds: &[&[&[u8]]] =
+            &[&[b"escrow", listing_key.as_ref(), &[self.listing.escrow_bump]]];
+
+        let collection_info = self.collection.as_ref().map(|c| c.to_account_info());
+        return_listed_asset(
+            &self.mpl_core_program.to_account_info(),
+            &self.asset.to_account_info(),
+            collection_info.as_ref(),
+            &self.escrow.to_account_info(),
+            &self.seller.to_account_info(),
+            &self.cranker.to_account_info(),
+            &self.system_program.to_account_info(),
+            escrow_seeds,
+        )?;
+
+        // The bounty comes out of the listing's rent, the rest is refunded to the seller by `close = seller`
+        let bounty = self
+            .marketplace
+            .expiry_bounty
+            .min(self.listing.get_lamports());
+        self.listing.sub_lamports(bounty)?;
+        self.cranker.add_lamports(bounty)?;
+
+        emit!(ListingExpired {
+            listing: listing_key,
+            asset: self.asset.key(),
+            seller: self.seller.key(),
+            cranker: self.cranker.key(),
+            bounty,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
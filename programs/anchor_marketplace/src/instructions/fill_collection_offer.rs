@@ -0,0 +1,333 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    types::UpdateAuthority,
+};
+
+use crate::delegate::{release_sold_asset, thaw_sold_asset};
+use crate::error::MarketplaceError;
+use crate::payment::{
+    close_escrow_token_account, pay_sale, payment_account, PaymentSource, SaleSplit,
+};
+use crate::{
+    AssetSold, CollectionConfig, CollectionOffer, CollectionOfferFilled, Marketplace,
+    MetalAttributes, PaymentMint, SaleRecord, SellerProfile,
+};
+
+#[derive(Accounts)]
+pub struct FillCollectionOffer<'info> {
+    /// Holder of the asset filling one unit of the offer
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        address = collection_offer.buyer,
+    )]
+    pub buyer: SystemAccount<'info>,
+
+    /// The MPL Core asset being sold, held in the seller's wallet
+    #[account(
+        mut,
+        constraint = asset.owner == seller.key() @ MarketplaceError::NotAssetOwner,
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()) @ MarketplaceError::CollectionMismatch,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    #[account(
+        mut,
+        address = collection_offer.collection @ MarketplaceError::CollectionMismatch,
+    )]
+    pub collection: Account<'info, BaseCollectionV1>,
+
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection.key().as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.tradable @ MarketplaceError::CollectionNotTradable,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.can_list(&seller.key(), &seller_profile) @ MarketplaceError::UnauthorizedSeller,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        seeds = [b"seller_profile", marketplace.key().as_ref(), seller.key().as_ref()],
+        bump,
+        space = 8 + SellerProfile::INIT_SPACE
+    )]
+    pub seller_profile: Account<'info, SellerProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"collection_offer", marketplace.key().as_ref(), buyer.key().as_ref(), collection_offer.id.to_le_bytes().as_ref()],
+        bump = collection_offer.bump,
+    )]
+    pub collection_offer: Account<'info, CollectionOffer>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        seeds = [b"sale_record", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump,
+        space = 8 + SaleRecord::INIT_SPACE
+    )]
+    pub sale_record: Account<'info, SaleRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    /// The accepted mint the offer was made in
+    #[account(
+        seeds = [b"payment_mint", marketplace.key().as_ref(), payment_mint_config.mint.as_ref()],
+        bump = payment_mint_config.bump,
+        constraint = payment_mint_config.mint == collection_offer.payment_mint @ MarketplaceError::PaymentMintMismatch,
+    )]
+    pub payment_mint_config: Account<'info, PaymentMint>,
+
+    /// CHECK: Feed converting the payment mint into USD for the sale record, validated against `payment_mint_config`
+    pub payment_price_feed: Option<UncheckedAccount<'info>>,
+
+    /// The SPL mint of the offer, omitted for SOL offers
+    #[account(
+        address = collection_offer.payment_mint @ MarketplaceError::PaymentMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = collection_offer,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = payment_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The asset's redemption, which must not exist for a frozen asset to be sold on
+    #[account(
+        seeds = [b"redemption", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump,
+    )]
+    pub redemption: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> FillCollectionOffer<'info> {
+    /// Sells one matching asset into the offer, closing it to the buyer once every unit is filled.
+    /// Creator wallets, or their payment token accounts for SPL offers, are passed as
+    /// `creator_accounts` in the order of the royalties plugin.
+    pub fn fill_collection_offer(
+        &mut self,
+        creator_accounts: &[AccountInfo<'info>],
+        bumps: &FillCollectionOfferBumps,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(
+            !self.collection_offer.is_expired(clock.unix_timestamp),
+            MarketplaceError::OfferExpired
+        );
+        require!(
+            self.payment_mint.is_some() != self.collection_offer.is_native_payment(),
+            MarketplaceError::PaymentMintMismatch
+        );
+
+        let attributes = MetalAttributes::from_asset(&self.asset.to_account_info())?;
+        require!(
+            self.collection_offer.matches(&attributes),
+            MarketplaceError::AssetDoesNotMatchOffer
+        );
+
+        if self.seller_profile.seller == Pubkey::default() {
+            self.seller_profile.set_inner(SellerProfile {
+                marketplace: self.marketplace.key(),
+                seller: self.seller.key(),
+                approved: false,
+                listing_count: 0,
+                sale_count: 0,
+                volume: 0,
+                bump: bumps.seller_profile,
+            });
+        }
+
+        let price = self.collection_offer.price;
+        let split = self.pay_seller(creator_accounts)?;
+        self.deliver_asset()?;
+
+        let payment_price_feed = self
+            .payment_price_feed
+            .as_ref()
+            .map(|feed| feed.to_account_info());
+        let usd_price = self.payment_mint_config.usd_from_amount(
+            price,
+            &self.marketplace.oracle,
+            payment_price_feed.as_ref(),
+        )?;
+
+        self.seller_profile.record_sale(usd_price)?;
+        self.sale_record.record(
+            AssetSold {
+                marketplace: self.marketplace.key(),
+                asset: self.asset.key(),
+                seller: self.seller.key(),
+                buyer: self.buyer.key(),
+                price: usd_price,
+                payment_mint: self.collection_offer.payment_mint,
+                amount: price,
+                fee: split.fee,
+                royalty: split.royalty,
+                slot: clock.slot,
+                timestamp: clock.unix_timestamp,
+            },
+            bumps.sale_record,
+        )?;
+
+        self.collection_offer.quantity -= 1;
+        emit!(CollectionOfferFilled {
+            offer: self.collection_offer.key(),
+            asset: self.asset.key(),
+            seller: self.seller.key(),
+            buyer: self.buyer.key(),
+            price,
+            remaining: self.collection_offer.quantity,
+            timestamp: clock.unix_timestamp,
+        });
+
+        if self.collection_offer.quantity == 0 {
+            self.collection_offer.close(self.buyer.to_account_info())?;
+        }
+
+        Ok(())
+    }
+
+    /// Pays one unit out of the escrow through the same split as a purchase, closing the escrow
+    /// token account to the buyer after the last unit
+    fn pay_seller(&self, creator_accounts: &[AccountInfo<'info>]) -> Result<SaleSplit> {
+        let marketplace_key = self.marketplace.key();
+        let offer_id = self.collection_offer.id.to_le_bytes();
+        let offer_seeds: &[&[&[u8]]] = &[&[
+            b"collection_offer",
+            marketplace_key.as_ref(),
+            self.collection_offer.buyer.as_ref(),
+            offer_id.as_ref(),
+            &[self.collection_offer.bump],
+        ]];
+
+        let payment_mint = self.payment_mint.as_ref();
+        let source = PaymentSource::Escrow {
+            escrow: self.collection_offer.to_account_info(),
+            token_account: self.escrow_payment_account.as_ref(),
+            token_program: self.token_program.to_account_info(),
+            signer_seeds: offer_seeds,
+        };
+
+        let collection = self.collection.to_account_info();
+        let split = pay_sale(
+            &source,
+            payment_mint,
+            &self.marketplace,
+            &self.asset.to_account_info(),
+            Some(&collection),
+            creator_accounts,
+            payment_account(
+                payment_mint,
+                self.treasury.to_account_info(),
+                self.treasury_payment_account.as_ref(),
+            )?,
+            payment_account(
+                payment_mint,
+                self.seller.to_account_info(),
+                self.seller_payment_account.as_ref(),
+            )?,
+            self.collection_offer.price,
+        )?;
+
+        if self.collection_offer.quantity == 1 {
+            close_escrow_token_account(
+                self.collection_offer.to_account_info(),
+                self.escrow_payment_account.as_ref(),
+                self.buyer.to_account_info(),
+                self.token_program.to_account_info(),
+                offer_seeds,
+            )?;
+        }
+
+        Ok(split)
+    }
+
+    fn deliver_asset(&self) -> Result<()> {
+        let mpl_core_program = self.mpl_core_program.to_account_info();
+        let asset = self.asset.to_account_info();
+        let collection = self.collection.to_account_info();
+        let marketplace = self.marketplace.to_account_info();
+        let seller = self.seller.to_account_info();
+        let system_program = self.system_program.to_account_info();
+
+        let marketplace_id = self.marketplace.id.to_le_bytes();
+        let marketplace_seeds: &[&[&[u8]]] = &[&[
+            b"marketplace",
+            marketplace_id.as_ref(),
+            &[self.marketplace.bump],
+        ]];
+
+        // An asset bought on the marketplace stays frozen under the marketplace until it is sold on
+        thaw_sold_asset(
+            &mpl_core_program,
+            &asset,
+            Some(&collection),
+            &self.redemption.to_account_info(),
+            &seller,
+            &marketplace,
+            &system_program,
+            marketplace_seeds,
+        )?;
+
+        release_sold_asset(
+            &mpl_core_program,
+            &asset,
+            Some(&collection),
+            &seller,
+            &self.buyer.to_account_info(),
+            &seller,
+            &marketplace,
+            &system_program,
+            &[],
+            marketplace_seeds,
+        )
+    }
+}
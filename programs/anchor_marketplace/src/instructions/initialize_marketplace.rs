@@ -1,10 +1,10 @@
 use anchor_lang::{prelude::*, solana_program::sysvar::rent, system_program::{self, Transfer}};
-use anchor_spl::token_interface::{Mint, TokenInterface};
+use anchor_spl::token_interface::TokenInterface;
 
-use crate::{error::MarketplaceError, Marketplace};
+use crate::{error::MarketplaceError, InitializeParams, Marketplace};
 
 #[derive(Accounts)]
-
+#[instruction(params: InitializeParams)]
 pub struct InitializeMarketplace<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -20,7 +20,7 @@ pub struct InitializeMarketplace<'info> {
     #[account(
         init,
         payer = admin,
-        seeds = [b"marketplace", admin.key().as_ref()],
+        seeds = [b"marketplace", params.id.to_le_bytes().as_ref()],
         bump,
         space = 8 + Marketplace::INIT_SPACE
     )]
@@ -41,15 +41,20 @@ pub struct InitializeMarketplace<'info> {
 }
 
 impl<'info> InitializeMarketplace<'info> {
-    pub fn handle(&mut self, name: String, fee_bps: u16, bumps: &InitializeMarketplaceBumps) -> Result<()>{
+    pub fn handle(&mut self, params: InitializeParams, bumps: &InitializeMarketplaceBumps) -> Result<()>{
+        let InitializeParams { id, name, fee_bps, fee_timelock, oracle, seller_permission } = params;
 
-        require!(name.len() < 4 + 32, MarketplaceError::NameTooLong);
-        require!(name.len() > 0, MarketplaceError::UndefinedName);
+        require!(name.len() <= 32, MarketplaceError::NameTooLong);
+        require!(!name.is_empty(), MarketplaceError::UndefinedName);
         require!(
             fee_bps <= 10000, 
             MarketplaceError::InvalidFeeBps
         );
-        self.marketplace.set_inner(Marketplace { admin: self.admin.key(), treasury_bump: bumps.treasury, bump: bumps.marketplace, fee_bps, name });
+        require!(
+            oracle.max_confidence_bps <= 10000,
+            MarketplaceError::InvalidConfidenceBps
+        );
+        self.marketplace.set_inner(Marketplace { id, admin: self.admin.key(), treasury_bump: bumps.treasury, bump: bumps.marketplace, fee_bps, name, oracle, fee_timelock, pending_fee: None, pending_fee_timelock: None, pending_admin: None, seller_permission, expiry_bounty: 0 });
 
         // create treasury account by transfering minimum amount for rent
         let amount_for_rent =  rent::Rent::get()?.minimum_balance(self.treasury.to_account_info().data_len());
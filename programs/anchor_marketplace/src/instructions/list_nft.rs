@@ -2,16 +2,13 @@ use anchor_lang::prelude::*;
 
 use mpl_core::{
     accounts::{BaseAssetV1, BaseCollectionV1},
-    instructions::{
-        AddPluginV1CpiBuilder, ApprovePluginAuthorityV1CpiBuilder, TransferV1CpiBuilder,
-    },
-    types::{
-        BurnDelegate, FreezeDelegate, Plugin, PluginAuthority, TransferDelegate, UpdateAuthority,
-    },
+    instructions::TransferV1CpiBuilder,
+    types::{BurnDelegate, FreezeDelegate, Plugin, TransferDelegate, UpdateAuthority},
 };
 
+use crate::delegate::{delegate_plugin, thaw_sold_asset};
 pub use crate::error::MarketplaceError;
-use crate::{Listing, Marketplace};
+use crate::{CollectionConfig, Listing, ListingKind, Marketplace, MetalAttributes, SellerProfile};
 
 #[derive(Accounts)]
 pub struct ListNFT<'info> {
@@ -22,23 +19,45 @@ pub struct ListNFT<'info> {
     #[account(
         mut,
         constraint = asset.owner == seller.key() @ MarketplaceError::NotAssetOwner,
-        constraint = asset.update_authority == UpdateAuthority::Address(seller.key()) @ MarketplaceError::NotUpdateAuthority,
+        constraint = match asset.update_authority {
+            // assets minted before the marketplace PDA became update authority are held by the admin
+            UpdateAuthority::Address(authority) => authority == marketplace.key() || authority == marketplace.admin,
+            UpdateAuthority::Collection(_) => collection.is_some(),
+            UpdateAuthority::None => false,
+        } @ MarketplaceError::NotUpdateAuthority,
     )]
     pub asset: Account<'info, BaseAssetV1>,
 
     /// The collection that this asset belongs to
     #[account(
-        constraint = asset.update_authority == UpdateAuthority::Address(collection.update_authority) @ MarketplaceError::CollectionMismatch,
+        mut,
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()) @ MarketplaceError::CollectionMismatch,
     )]
     pub collection: Option<Account<'info, BaseCollectionV1>>,
 
+    /// Registration of `collection`, which must be tradable for its assets to be listed
     #[account(
-        seeds = [b"marketplace", seller.key().as_ref()],
+        seeds = [b"collection", marketplace.key().as_ref(), collection_config.collection.as_ref()],
+        bump = collection_config.bump,
+    )]
+    pub collection_config: Option<Account<'info, CollectionConfig>>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
         bump = marketplace.bump,
-        constraint = seller.key() == marketplace.admin @ MarketplaceError::UnauthorizedCreator
+        constraint = marketplace.can_list(&seller.key(), &seller_profile) @ MarketplaceError::UnauthorizedSeller,
     )]
     pub marketplace: Account<'info, Marketplace>,
 
+    #[account(
+        init_if_needed,
+        payer = seller,
+        seeds = [b"seller_profile", marketplace.key().as_ref(), seller.key().as_ref()],
+        bump,
+        space = 8 + SellerProfile::INIT_SPACE
+    )]
+    pub seller_profile: Account<'info, SellerProfile>,
+
     #[account(
         init,
         payer = seller,
@@ -56,6 +75,13 @@ pub struct ListNFT<'info> {
     )]
     pub escrow: UncheckedAccount<'info>,
 
+    /// CHECK: The asset's redemption, which must not exist for a frozen asset to be relisted
+    #[account(
+        seeds = [b"redemption", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump,
+    )]
+    pub redemption: UncheckedAccount<'info>,
+
     /// CHECK: MPL Core program
     #[account(address = mpl_core::ID)]
     pub mpl_core_program: UncheckedAccount<'info>,
@@ -68,6 +94,38 @@ impl<'info> ListNFT<'info> {
         params: InitializeListingParams,
         bumps: &ListNFTBumps,
     ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let kind = params.kind.into_kind(&self.asset.to_account_info())?;
+        kind.validate(now)?;
+        if let Some(expires_at) = params.expires_at {
+            // Auctions end through `settle_auction` instead
+            require!(
+                expires_at > now && !matches!(kind, ListingKind::Auction { .. }),
+                MarketplaceError::InvalidListingExpiry
+            );
+        }
+        if let Some(collection) = &self.collection {
+            require!(
+                self.collection_config.as_ref().is_some_and(|config| {
+                    config.collection == collection.key() && config.tradable
+                }),
+                MarketplaceError::CollectionNotTradable
+            );
+        }
+
+        if self.seller_profile.seller == Pubkey::default() {
+            self.seller_profile.set_inner(SellerProfile {
+                marketplace: self.marketplace.key(),
+                seller: self.seller.key(),
+                approved: false,
+                listing_count: 0,
+                sale_count: 0,
+                volume: 0,
+                bump: bumps.seller_profile,
+            });
+        }
+        self.seller_profile.record_listing()?;
+
         self.listing.set_inner(Listing {
             seller: self.seller.key(),
             mint: self.asset.key(),
@@ -76,75 +134,56 @@ impl<'info> ListNFT<'info> {
             escrow_bump: bumps.escrow,
             is_active: true,
             token_id: params.token_id,
+            kind,
+            expires_at: params.expires_at,
         });
         Ok(())
     }
 
     pub fn list_nft(&mut self) -> Result<()> {
-        // Transfer the MPL Core asset to the escrow account
-        // note that I'm passing authority on all CPIBuilders because The owner is the authority (owner) of the asset, it's not delegated so owner can sign on behalf of this tx and as the authority of the asset
-        // add TransferDelegate Plugin
-        AddPluginV1CpiBuilder::new(&self.mpl_core_program.to_account_info())
-            .authority(Some(&self.seller.to_account_info()))
-            .asset(&self.asset.to_account_info())
-            .payer(&self.seller.to_account_info())
-            .plugin(Plugin::TransferDelegate(TransferDelegate {}))
-            .system_program(&self.system_program.to_account_info())
-            .invoke()?;
+        let mpl_core_program = self.mpl_core_program.to_account_info();
+        let asset = self.asset.to_account_info();
+        let collection = self.collection.as_ref().map(|c| c.to_account_info());
+        let seller = self.seller.to_account_info();
+        let system_program = self.system_program.to_account_info();
 
-        // Approve TransferDelegate Plugin to listing
-        ApprovePluginAuthorityV1CpiBuilder::new(&self.mpl_core_program.to_account_info())
-            .new_authority(PluginAuthority::Address {
-                address: self.listing.key(),
-            })
-            .authority(Some(&self.seller.to_account_info()))
-            .asset(&self.asset.to_account_info())
-            .payer(&self.seller.to_account_info())
-            .plugin_type(mpl_core::types::PluginType::TransferDelegate)
-            .system_program(&self.system_program.to_account_info())
-            .invoke()?;
+        // An asset bought on the marketplace stays frozen under the marketplace until its owner relists it
+        let marketplace_id = self.marketplace.id.to_le_bytes();
+        let marketplace_seeds: &[&[&[u8]]] = &[&[
+            b"marketplace",
+            marketplace_id.as_ref(),
+            &[self.marketplace.bump],
+        ]];
+        thaw_sold_asset(
+            &mpl_core_program,
+            &asset,
+            collection.as_ref(),
+            &self.redemption.to_account_info(),
+            &seller,
+            &self.marketplace.to_account_info(),
+            &system_program,
+            marketplace_seeds,
+        )?;
 
-        // add BurnDelegate Plugin
-        AddPluginV1CpiBuilder::new(&self.mpl_core_program.to_account_info())
-            .authority(Some(&self.seller.to_account_info()))
-            .asset(&self.asset.to_account_info())
-            .payer(&self.seller.to_account_info())
-            .plugin(Plugin::BurnDelegate(BurnDelegate {}))
-            .system_program(&self.system_program.to_account_info())
-            .invoke()?;
-
-        // approve BurnDelegate Plugin to listing
-        ApprovePluginAuthorityV1CpiBuilder::new(&self.mpl_core_program.to_account_info())
-            .new_authority(PluginAuthority::Address {
-                address: self.listing.key(),
-            })
-            .authority(Some(&self.seller.to_account_info()))
-            .asset(&self.asset.to_account_info())
-            .payer(&self.seller.to_account_info())
-            .plugin_type(mpl_core::types::PluginType::BurnDelegate)
-            .system_program(&self.system_program.to_account_info())
-            .invoke()?;
-
-        // add Freeze Delegate
-        AddPluginV1CpiBuilder::new(&self.mpl_core_program.to_account_info())
-            .authority(Some(&self.seller.to_account_info()))
-            .asset(&self.asset.to_account_info())
-            .payer(&self.seller.to_account_info())
-            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
-            .system_program(&self.system_program.to_account_info())
-            .invoke()?;
-
-        // Approve Freeze Delegate to listing
-        ApprovePluginAuthorityV1CpiBuilder::new(&self.mpl_core_program.to_account_info())
-            .new_authority(PluginAuthority::Address {
-                address: self.listing.key(),
-            })
-            .authority(Some(&self.seller.to_account_info()))
-            .asset(&self.asset.to_account_info())
-            .payer(&self.seller.to_account_info())
-            .plugin_type(mpl_core::types::PluginType::FreezeDelegate)
-            .system_program(&self.system_program.to_account_info())
-            .invoke()?;
+        // Delegate transfer, burn and freeze to the listing, reusing the plugins a previous sale left on the asset
+        // note that the seller signs as authority because the owner is the authority (owner) of the asset, it's not delegated
+        for plugin in [
+            Plugin::TransferDelegate(TransferDelegate {}),
+            Plugin::BurnDelegate(BurnDelegate {}),
+            Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+        ] {
+            delegate_plugin(
+                &mpl_core_program,
+                &asset,
+                collection.as_ref(),
+                &seller,
+                &seller,
+                &system_program,
+                plugin,
+                self.listing.key(),
+                &[],
+            )?;
+        }
 
         // Transfer token to Escrow -> Now Listing can make Tx on behalf of Escrow for the Asset
         TransferV1CpiBuilder::new(&self.mpl_core_program.to_account_info())
@@ -163,4 +202,76 @@ impl<'info> ListNFT<'info> {
 pub struct InitializeListingParams {
     pub price: u64,
     pub token_id: u16,
+    pub kind: ListingKindParams,
+    pub expires_at: Option<i64>,
+}
+
+/// How a new listing is priced, see `ListingKind` for the meaning of each field
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub enum ListingKindParams {
+    Fixed,
+    /// The metal, weight and purity are read from the asset's attributes
+    Spot {
+        premium_bps: u16,
+    },
+    Auction {
+        payment_mint: Pubkey,
+        reserve_price: u64,
+        min_bid_increment: u64,
+        start_time: i64,
+        end_time: i64,
+        extension_secs: u32,
+    },
+    Dutch {
+        start_price: u64,
+        floor_price: u64,
+        start_time: i64,
+        end_time: i64,
+    },
+}
+
+impl ListingKindParams {
+    pub fn into_kind(self, asset: &AccountInfo) -> Result<ListingKind> {
+        let kind = match self {
+            ListingKindParams::Fixed => ListingKind::Fixed,
+            ListingKindParams::Spot { premium_bps } => {
+                let attributes = MetalAttributes::from_asset(asset)?;
+                ListingKind::Spot {
+                    metal: attributes.metal,
+                    weight_mg: attributes.weight_mg,
+                    purity: attributes.purity,
+                    premium_bps,
+                }
+            }
+            ListingKindParams::Auction {
+                payment_mint,
+                reserve_price,
+                min_bid_increment,
+                start_time,
+                end_time,
+                extension_secs,
+            } => ListingKind::Auction {
+                payment_mint,
+                reserve_price,
+                min_bid_increment,
+                start_time,
+                end_time,
+                extension_secs,
+                highest_bid: 0,
+                highest_bidder: None,
+            },
+            ListingKindParams::Dutch {
+                start_price,
+                floor_price,
+                start_time,
+                end_time,
+            } => ListingKind::Dutch {
+                start_price,
+                floor_price,
+                start_time,
+                end_time,
+            },
+        };
+        Ok(kind)
+    }
 }
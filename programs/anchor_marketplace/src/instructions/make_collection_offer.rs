@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::error::MarketplaceError;
+use crate::payment::{payment_account, payment_program, transfer_payment};
+use crate::{
+    CollectionConfig, CollectionOffer, CollectionOfferMade, Marketplace, MetalType, PaymentMint,
+};
+
+#[derive(Accounts)]
+#[instruction(params: MakeCollectionOfferParams)]
+pub struct MakeCollectionOffer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    /// Registration of the collection the offer is for, which must be tradable
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection_config.collection.as_ref()],
+        bump = collection_config.bump,
+        constraint = collection_config.tradable @ MarketplaceError::CollectionNotTradable,
+        constraint = collection_config.metal == params.metal @ MarketplaceError::CollectionMetalMismatch,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init,
+        payer = buyer,
+        seeds = [b"collection_offer", marketplace.key().as_ref(), buyer.key().as_ref(), params.id.to_le_bytes().as_ref()],
+        bump,
+        space = 8 + CollectionOffer::INIT_SPACE
+    )]
+    pub collection_offer: Account<'info, CollectionOffer>,
+
+    /// The accepted mint the offer is made in
+    #[account(
+        seeds = [b"payment_mint", marketplace.key().as_ref(), payment_mint_config.mint.as_ref()],
+        bump = payment_mint_config.bump,
+        constraint = payment_mint_config.enabled @ MarketplaceError::PaymentMintDisabled,
+    )]
+    pub payment_mint_config: Account<'info, PaymentMint>,
+
+    /// The SPL mint being offered, omitted when offering SOL
+    #[account(
+        address = payment_mint_config.mint @ MarketplaceError::PaymentMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token account escrowing the offer until every unit is filled or it is cancelled
+    #[account(
+        init,
+        payer = buyer,
+        associated_token::mint = payment_mint,
+        associated_token::authority = collection_offer,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> MakeCollectionOffer<'info> {
+    pub fn make_collection_offer(
+        &mut self,
+        params: MakeCollectionOfferParams,
+        bumps: &MakeCollectionOfferBumps,
+    ) -> Result<()> {
+        require!(
+            self.payment_mint.is_some() != self.payment_mint_config.is_native(),
+            MarketplaceError::PaymentMintMismatch
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            params.price > 0 && params.quantity > 0 && params.expires_at > now,
+            MarketplaceError::InvalidOffer
+        );
+        require!(params.min_purity < 10_000, MarketplaceError::InvalidPurity);
+
+        self.collection_offer.set_inner(CollectionOffer {
+            marketplace: self.marketplace.key(),
+            buyer: self.buyer.key(),
+            collection: self.collection_config.collection,
+            id: params.id,
+            metal: params.metal,
+            min_purity: params.min_purity,
+            min_weight_mg: params.min_weight_mg,
+            price: params.price,
+            quantity: params.quantity,
+            payment_mint: self.payment_mint_config.mint,
+            created_at: now,
+            expires_at: params.expires_at,
+            bump: bumps.collection_offer,
+        });
+
+        // Escrow every unit up front so each fill is paid out of the offer
+        let payment_mint = self.payment_mint.as_ref();
+        transfer_payment(
+            payment_mint,
+            payment_account(
+                payment_mint,
+                self.buyer.to_account_info(),
+                self.buyer_payment_account.as_ref(),
+            )?,
+            payment_account(
+                payment_mint,
+                self.collection_offer.to_account_info(),
+                self.escrow_payment_account.as_ref(),
+            )?,
+            self.buyer.to_account_info(),
+            payment_program(
+                payment_mint,
+                self.token_program.to_account_info(),
+                self.system_program.to_account_info(),
+            ),
+            self.collection_offer.escrowed_amount()?,
+            &[],
+        )?;
+
+        emit!(CollectionOfferMade {
+            offer: self.collection_offer.key(),
+            collection: self.collection_config.collection,
+            buyer: self.buyer.key(),
+            metal: params.metal,
+            min_purity: params.min_purity,
+            min_weight_mg: params.min_weight_mg,
+            price: params.price,
+            quantity: params.quantity,
+            payment_mint: self.payment_mint_config.mint,
+            expires_at: params.expires_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct MakeCollectionOfferParams {
+    pub id: u64,
+    pub metal: MetalType,
+    /// Lowest accepted fineness in parts per 10,000
+    pub min_purity: u16,
+    pub min_weight_mg: u64,
+    /// Price per unit in units of the payment mint
+    pub price: u64,
+    pub quantity: u32,
+    pub expires_at: i64,
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use mpl_core::accounts::BaseAssetV1;
+
+use crate::error::MarketplaceError;
+use crate::payment::{payment_account, payment_program, transfer_payment};
+use crate::{Marketplace, Offer, OfferMade, PaymentMint};
+
+#[derive(Accounts)]
+pub struct MakeOffer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// The MPL Core asset being bid on, listed or not
+    pub asset: Account<'info, BaseAssetV1>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        init,
+        payer = buyer,
+        seeds = [b"offer", marketplace.key().as_ref(), asset.key().as_ref(), buyer.key().as_ref()],
+        bump,
+        space = 8 + Offer::INIT_SPACE
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// The accepted mint the offer is made in
+    #[account(
+        seeds = [b"payment_mint", marketplace.key().as_ref(), payment_mint_config.mint.as_ref()],
+        bump = payment_mint_config.bump,
+        constraint = payment_mint_config.enabled @ MarketplaceError::PaymentMintDisabled,
+    )]
+    pub payment_mint_config: Account<'info, PaymentMint>,
+
+    /// The SPL mint being offered, omitted when offering SOL
+    #[account(
+        address = payment_mint_config.mint @ MarketplaceError::PaymentMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token account escrowing the offer until it is accepted or cancelled
+    #[account(
+        init,
+        payer = buyer,
+        associated_token::mint = payment_mint,
+        associated_token::authority = offer,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> MakeOffer<'info> {
+    pub fn make_offer(&mut self, params: MakeOfferParams, bumps: &MakeOfferBumps) -> Result<()> {
+        require!(
+            self.payment_mint.is_some() != self.payment_mint_config.is_native(),
+            MarketplaceError::PaymentMintMismatch
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            params.amount > 0 && params.expires_at > now,
+            MarketplaceError::InvalidOffer
+        );
+
+        let payment_mint = self.payment_mint.as_ref();
+        transfer_payment(
+            payment_mint,
+            payment_account(
+                payment_mint,
+                self.buyer.to_account_info(),
+                self.buyer_payment_account.as_ref(),
+            )?,
+            payment_account(
+                payment_mint,
+                self.offer.to_account_info(),
+                self.escrow_payment_account.as_ref(),
+            )?,
+            self.buyer.to_account_info(),
+            payment_program(
+                payment_mint,
+                self.token_program.to_account_info(),
+                self.system_program.to_account_info(),
+            ),
+            params.amount,
+            &[],
+        )?;
+
+        self.offer.set_inner(Offer {
+            marketplace: self.marketplace.key(),
+            asset: self.asset.key(),
+            buyer: self.buyer.key(),
+            amount: params.amount,
+            payment_mint: self.payment_mint_config.mint,
+            created_at: now,
+            expires_at: params.expires_at,
+            bump: bumps.offer,
+        });
+
+        emit!(OfferMade {
+            offer: self.offer.key(),
+            asset: self.asset.key(),
+            buyer: self.buyer.key(),
+            amount: params.amount,
+            payment_mint: self.payment_mint_config.mint,
+            expires_at: params.expires_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct MakeOfferParams {
+    /// Amount offered in units of the payment mint
+    pub amount: u64,
+    pub expires_at: i64,
+}
@@ -13,5 +13,95 @@ pub use create_nft::*;
 pub mod update_nft;
 pub use update_nft::*;
 
-pub mod redeem;
-pub use redeem::*;
+pub mod request_redemption;
+pub use request_redemption::*;
+
+pub mod accept_redemption;
+pub use accept_redemption::*;
+
+pub mod reject_redemption;
+pub use reject_redemption::*;
+
+pub mod cancel_redemption;
+pub use cancel_redemption::*;
+
+pub mod confirm_shipment;
+pub use confirm_shipment::*;
+
+pub mod cancel_listing;
+pub use cancel_listing::*;
+
+pub mod update_listing;
+pub use update_listing::*;
+
+pub mod add_payment_mint;
+pub use add_payment_mint::*;
+
+pub mod update_payment_mint;
+pub use update_payment_mint::*;
+
+pub mod create_vault;
+pub use create_vault::*;
+
+pub mod update_vault;
+pub use update_vault::*;
+
+pub mod register_vault_partner;
+pub use register_vault_partner::*;
+
+pub mod suspend_vault_partner;
+pub use suspend_vault_partner::*;
+
+pub mod remove_vault_partner;
+pub use remove_vault_partner::*;
+
+pub mod set_partner_vaults;
+pub use set_partner_vaults::*;
+
+pub mod withdraw_treasury;
+pub use withdraw_treasury::*;
+
+pub mod update_marketplace_config;
+pub use update_marketplace_config::*;
+
+pub mod propose_admin;
+pub use propose_admin::*;
+
+pub mod accept_admin;
+pub use accept_admin::*;
+
+pub mod set_seller_approval;
+pub use set_seller_approval::*;
+
+pub mod create_collection;
+pub use create_collection::*;
+
+pub mod update_collection;
+pub use update_collection::*;
+
+pub mod place_bid;
+pub use place_bid::*;
+
+pub mod settle_auction;
+pub use settle_auction::*;
+
+pub mod make_offer;
+pub use make_offer::*;
+
+pub mod cancel_offer;
+pub use cancel_offer::*;
+
+pub mod accept_offer;
+pub use accept_offer::*;
+
+pub mod make_collection_offer;
+pub use make_collection_offer::*;
+
+pub mod cancel_collection_offer;
+pub use cancel_collection_offer::*;
+
+pub mod fill_collection_offer;
+pub use fill_collection_offer::*;
+
+pub mod expire_listing;
+pub use expire_listing::*;
@@ -0,0 +1,229 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::error::MarketplaceError;
+use crate::payment::{
+    close_escrow_token_account, payment_account, payment_program, release_escrowed_payment,
+    transfer_payment,
+};
+use crate::{Bid, BidPlaced, Listing, Marketplace, PaymentMint};
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"listing", marketplace.key().as_ref(), listing.mint.as_ref()],
+        bump = listing.bump,
+        constraint = listing.is_active @ MarketplaceError::ListingNotActive,
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// Already exists when the highest bidder raises their own bid
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"bid", listing.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = 8 + Bid::INIT_SPACE
+    )]
+    pub bid: Account<'info, Bid>,
+
+    /// The bidder being outbid, required once the auction has bids from someone else
+    #[account(
+        mut,
+        constraint = Some(previous_bidder.key()) == listing.highest_bidder() @ MarketplaceError::BidMismatch,
+    )]
+    pub previous_bidder: Option<SystemAccount<'info>>,
+
+    /// The bid being outbid, refunded and closed to `previous_bidder`
+    #[account(
+        mut,
+        seeds = [b"bid", listing.key().as_ref(), previous_bid.bidder.as_ref()],
+        bump = previous_bid.bump,
+        constraint = Some(previous_bid.bidder) == listing.highest_bidder() @ MarketplaceError::BidMismatch,
+    )]
+    pub previous_bid: Option<Account<'info, Bid>>,
+
+    /// The accepted mint the auction is bid in
+    #[account(
+        seeds = [b"payment_mint", marketplace.key().as_ref(), payment_mint_config.mint.as_ref()],
+        bump = payment_mint_config.bump,
+        constraint = payment_mint_config.enabled @ MarketplaceError::PaymentMintDisabled,
+    )]
+    pub payment_mint_config: Account<'info, PaymentMint>,
+
+    /// The SPL mint being bid with, omitted when bidding in SOL
+    #[account(
+        address = payment_mint_config.mint @ MarketplaceError::PaymentMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub bidder_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token account escrowing the bid until it is outbid or settled
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = payment_mint,
+        associated_token::authority = bid,
+        associated_token::token_program = token_program,
+    )]
+    pub bid_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = previous_bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub previous_bidder_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = previous_bid,
+        associated_token::token_program = token_program,
+    )]
+    pub previous_bid_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> PlaceBid<'info> {
+    pub fn place_bid(&mut self, amount: u64, bumps: &PlaceBidBumps) -> Result<()> {
+        require!(
+            self.payment_mint.is_some() != self.payment_mint_config.is_native(),
+            MarketplaceError::PaymentMintMismatch
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let outbid = self.listing.highest_bidder();
+        let raising = outbid == Some(self.bidder.key());
+        // Only the highest bidder keeps a bid open, every outbid bid is closed on refund
+        require!(
+            raising || self.bid.bidder == Pubkey::default(),
+            MarketplaceError::BidMismatch
+        );
+        self.listing.place_bid(
+            self.bidder.key(),
+            amount,
+            &self.payment_mint_config.mint,
+            now,
+        )?;
+
+        // The highest bidder raising their own bid only escrows the difference
+        let escrowed = if raising { self.bid.amount } else { 0 };
+        if outbid.is_some() && !raising {
+            self.refund_previous_bid()?;
+        }
+
+        // Escrow the bid in the bid account until it is outbid or the auction settles
+        let payment_mint = self.payment_mint.as_ref();
+        transfer_payment(
+            payment_mint,
+            payment_account(
+                payment_mint,
+                self.bidder.to_account_info(),
+                self.bidder_payment_account.as_ref(),
+            )?,
+            payment_account(
+                payment_mint,
+                self.bid.to_account_info(),
+                self.bid_payment_account.as_ref(),
+            )?,
+            self.bidder.to_account_info(),
+            payment_program(
+                payment_mint,
+                self.token_program.to_account_info(),
+                self.system_program.to_account_info(),
+            ),
+            amount
+                .checked_sub(escrowed)
+                .ok_or(MarketplaceError::MathOverflowError)?,
+            &[],
+        )?;
+
+        self.bid.set_inner(Bid {
+            listing: self.listing.key(),
+            bidder: self.bidder.key(),
+            amount,
+            payment_mint: self.payment_mint_config.mint,
+            placed_at: now,
+            bump: bumps.bid,
+        });
+
+        emit!(BidPlaced {
+            listing: self.listing.key(),
+            asset: self.listing.mint,
+            bidder: self.bidder.key(),
+            amount,
+            outbid: outbid.filter(|_| !raising),
+            end_time: self.listing.auction_end_time()?,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the outbid bidder's escrowed funds and closes their bid
+    fn refund_previous_bid(&self) -> Result<()> {
+        let (Some(previous_bid), Some(previous_bidder)) =
+            (&self.previous_bid, &self.previous_bidder)
+        else {
+            return err!(MarketplaceError::BidMismatch);
+        };
+
+        let listing_key = self.listing.key();
+        let bid_seeds: &[&[&[u8]]] = &[&[
+            b"bid",
+            listing_key.as_ref(),
+            previous_bid.bidder.as_ref(),
+            &[previous_bid.bump],
+        ]];
+
+        let payment_mint = self.payment_mint.as_ref();
+        release_escrowed_payment(
+            payment_mint,
+            previous_bid.to_account_info(),
+            self.previous_bid_payment_account.as_ref(),
+            payment_account(
+                payment_mint,
+                previous_bidder.to_account_info(),
+                self.previous_bidder_payment_account.as_ref(),
+            )?,
+            self.token_program.to_account_info(),
+            previous_bid.amount,
+            bid_seeds,
+        )?;
+        close_escrow_token_account(
+            previous_bid.to_account_info(),
+            self.previous_bid_payment_account.as_ref(),
+            previous_bidder.to_account_info(),
+            self.token_program.to_account_info(),
+            bid_seeds,
+        )?;
+
+        previous_bid.close(previous_bidder.to_account_info())
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::{AdminProposed, Marketplace};
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+}
+
+impl<'info> ProposeAdmin<'info> {
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        // Proposing again replaces any earlier proposal
+        self.marketplace.pending_admin = Some(new_admin);
+
+        emit!(AdminProposed {
+            marketplace: self.marketplace.key(),
+            admin: self.admin.key(),
+            pending_admin: new_admin,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
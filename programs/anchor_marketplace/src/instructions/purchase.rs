@@ -1,14 +1,17 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
-use mpl_core::instructions::UpdatePluginV1CpiBuilder;
-use mpl_core::types::{FreezeDelegate, PermanentBurnDelegate, Plugin};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 use mpl_core::{
     accounts::{BaseAssetV1, BaseCollectionV1},
-    instructions::TransferV1CpiBuilder,
+    types::UpdateAuthority,
 };
 
+use crate::delegate::release_sold_asset;
 use crate::error::MarketplaceError;
-use crate::{Listing, Marketplace};
+use crate::payment::{pay_sale, payment_account, payment_program, PaymentSource};
+use crate::{AssetSold, Listing, Marketplace, PaymentMint, SaleRecord, SellerProfile};
 
 #[derive(Accounts)]
 pub struct Purchase<'info> {
@@ -26,10 +29,15 @@ pub struct Purchase<'info> {
     #[account(
         mut,
         constraint = asset.key() == listing.mint @ MarketplaceError::AssetMismatch,
+        constraint = matches!(asset.update_authority, UpdateAuthority::Collection(_)) == collection.is_some() @ MarketplaceError::CollectionMismatch,
     )]
     pub asset: Account<'info, BaseAssetV1>,
 
     /// The collection that this asset belongs to
+    #[account(
+        mut,
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()) @ MarketplaceError::CollectionMismatch,
+    )]
     pub collection: Option<Account<'info, BaseCollectionV1>>,
 
     /// CHECK: The escrow account that currently holds the asset
@@ -45,16 +53,36 @@ pub struct Purchase<'info> {
         mut,
         seeds = [b"listing", marketplace.key().as_ref(), asset.key().as_ref()],
         bump = listing.bump,
+        close = seller,
         constraint = listing.is_active @ MarketplaceError::ListingNotActive,
     )]
     pub listing: Account<'info, Listing>,
 
     #[account(
-        seeds = [b"marketplace", seller.key().as_ref()],
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
         bump = marketplace.bump,
     )]
     pub marketplace: Account<'info, Marketplace>,
 
+    /// Created here for sellers whose listings predate seller profiles
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        seeds = [b"seller_profile", marketplace.key().as_ref(), seller.key().as_ref()],
+        bump,
+        space = 8 + SellerProfile::INIT_SPACE
+    )]
+    pub seller_profile: Account<'info, SellerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        seeds = [b"sale_record", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump,
+        space = 8 + SaleRecord::INIT_SPACE
+    )]
+    pub sale_record: Account<'info, SaleRecord>,
+
     #[account(
         mut,
         seeds = [b"treasury", marketplace.key().as_ref()],
@@ -62,88 +90,185 @@ pub struct Purchase<'info> {
     )]
     pub treasury: SystemAccount<'info>,
 
+    /// The accepted mint the buyer chose to pay with
+    #[account(
+        seeds = [b"payment_mint", marketplace.key().as_ref(), payment_mint_config.mint.as_ref()],
+        bump = payment_mint_config.bump,
+        constraint = payment_mint_config.enabled @ MarketplaceError::PaymentMintDisabled,
+    )]
+    pub payment_mint_config: Account<'info, PaymentMint>,
+
+    /// CHECK: Feed converting USD into the payment mint, validated against `payment_mint_config`
+    pub payment_price_feed: Option<UncheckedAccount<'info>>,
+
+    /// The SPL mint being paid with, omitted when paying in SOL
+    #[account(
+        address = payment_mint_config.mint @ MarketplaceError::PaymentMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = payment_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Spot price feed for spot priced listings, validated against the marketplace oracle config
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
     /// CHECK: MPL Core program
     #[account(address = mpl_core::ID)]
     pub mpl_core_program: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 impl<'info> Purchase<'info> {
-    pub fn make_payment(&mut self) -> Result<()> {
-        let token_price = self.listing.get_price_by_token_id();
-        let marketplace_fee = self.marketplace.fee_bps as u64;
-
-        // Calculate fee to transfer to marketplace treasury
-        let amount_to_transfer_as_fee = token_price
-            .checked_mul(marketplace_fee)
-            .and_then(|mul_result| mul_result.checked_div(10_000))
-            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
-
-        // Calculate remaining amount to transfer to seller
-        let amount_to_transfer_to_seller = token_price
-            .checked_sub(amount_to_transfer_as_fee)
-            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
-
-        // Transfer fee to treasury
-        let cpi_account_fee_ix = Transfer {
-            from: self.buyer.to_account_info(),
-            to: self.treasury.to_account_info(),
-        };
+    /// Creator wallets, or their payment token accounts when paying in SPL tokens, are passed
+    /// as `creator_accounts` in the order of the royalties plugin
+    pub fn make_payment(
+        &mut self,
+        creator_accounts: &[AccountInfo<'info>],
+        bumps: &PurchaseBumps,
+    ) -> Result<()> {
+        require!(
+            self.payment_mint.is_some() != self.payment_mint_config.is_native(),
+            MarketplaceError::PaymentMintMismatch
+        );
 
-        transfer(
-            CpiContext::new(self.system_program.to_account_info(), cpi_account_fee_ix),
-            amount_to_transfer_as_fee,
-        )?;
+        require!(
+            !self.listing.is_expired(Clock::get()?.unix_timestamp),
+            MarketplaceError::ListingExpired
+        );
 
-        // Transfer remaining amount to seller
-        let cpi_account_amount_seller_ix = Transfer {
-            from: self.buyer.to_account_info(),
-            to: self.seller.to_account_info(),
-        };
+        let price_feed = self.price_feed.as_ref().map(|feed| feed.to_account_info());
+        let usd_price = self
+            .listing
+            .get_price_by_token_id(&self.marketplace.oracle, price_feed.as_ref())?;
+
+        let payment_price_feed = self
+            .payment_price_feed
+            .as_ref()
+            .map(|feed| feed.to_account_info());
+        let token_price = self.payment_mint_config.amount_from_usd(
+            usd_price,
+            &self.marketplace.oracle,
+            payment_price_feed.as_ref(),
+        )?;
 
-        transfer(
-            CpiContext::new(
+        let payment_mint = self.payment_mint.as_ref();
+        let source = PaymentSource::Wallet {
+            wallet: self.buyer.to_account_info(),
+            account: payment_account(
+                payment_mint,
+                self.buyer.to_account_info(),
+                self.buyer_payment_account.as_ref(),
+            )?,
+            program: payment_program(
+                payment_mint,
+                self.token_program.to_account_info(),
                 self.system_program.to_account_info(),
-                cpi_account_amount_seller_ix,
             ),
-            amount_to_transfer_to_seller,
+        };
+
+        let collection = self.collection.as_ref().map(|c| c.to_account_info());
+        let split = pay_sale(
+            &source,
+            payment_mint,
+            &self.marketplace,
+            &self.asset.to_account_info(),
+            collection.as_ref(),
+            creator_accounts,
+            payment_account(
+                payment_mint,
+                self.treasury.to_account_info(),
+                self.treasury_payment_account.as_ref(),
+            )?,
+            payment_account(
+                payment_mint,
+                self.seller.to_account_info(),
+                self.seller_payment_account.as_ref(),
+            )?,
+            token_price,
         )?;
 
-        Ok(())
+        if self.seller_profile.seller == Pubkey::default() {
+            self.seller_profile.set_inner(SellerProfile {
+                marketplace: self.marketplace.key(),
+                seller: self.seller.key(),
+                approved: false,
+                listing_count: 0,
+                sale_count: 0,
+                volume: 0,
+                bump: bumps.seller_profile,
+            });
+        }
+        self.seller_profile.record_sale(usd_price)?;
+
+        let clock = Clock::get()?;
+        self.sale_record.record(
+            AssetSold {
+                marketplace: self.marketplace.key(),
+                asset: self.asset.key(),
+                seller: self.seller.key(),
+                buyer: self.buyer.key(),
+                price: usd_price,
+                payment_mint: self.payment_mint_config.mint,
+                amount: token_price,
+                fee: split.fee,
+                royalty: split.royalty,
+                slot: clock.slot,
+                timestamp: clock.unix_timestamp,
+            },
+            bumps.sale_record,
+        )
     }
 
     pub fn transfer_nft(&mut self) -> Result<()> {
         let listing_key = &self.listing.key();
-        let signers_seeds: &[&[&[u8]]] = &[&[
-            b"escrow",
-            &listing_key.as_ref(),
-            &[self.listing.escrow_bump],
-        ]];
+        let signers_seeds: &[&[&[u8]]] =
+            &[&[b"escrow", listing_key.as_ref(), &[self.listing.escrow_bump]]];
 
-        let listing_seeds: &[&[&[u8]]] = &[&[
-            b"listing",
-            &self.marketplace.key().to_bytes(),
-            &self.asset.key().to_bytes(),
-            &[self.listing.bump],
+        let marketplace_id = self.marketplace.id.to_le_bytes();
+        let marketplace_seeds: &[&[&[u8]]] = &[&[
+            b"marketplace",
+            marketplace_id.as_ref(),
+            &[self.marketplace.bump],
         ]];
 
-        // note that I'm passing authority on this CPIBuilder because The owner is now expected to be escrow  since the asset was TRANSFERRED to escrow, escrow is the authority (owner) of the asset, it's not delegated so escrow can sign on behalf of this tx and as the authority of the asset
-        // Transfer the MPL Core asset from escrow to buyer
-        TransferV1CpiBuilder::new(&self.mpl_core_program.to_account_info())
-            .asset(&self.asset.to_account_info())
-            .payer(&self.buyer.to_account_info())
-            .authority(Some(&self.escrow.to_account_info()))
-            .new_owner(&self.buyer.to_account_info())
-            .system_program(Some(&self.system_program.to_account_info()))
-            .invoke_signed(signers_seeds)?;
-
-        // note that I'm not passing authority because Listing isnt the authority (owner) of the asset, it was delegated to listing so listing can sign on behalf of this tx
-        UpdatePluginV1CpiBuilder::new(&self.mpl_core_program.to_account_info())
-            .asset(&self.asset.to_account_info())
-            .payer(&self.buyer.to_account_info())
-            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: true }))
-            .system_program(&self.system_program.to_account_info())
-            .invoke_signed(listing_seeds)?;
-        Ok(())
+        let collection = self.collection.as_ref().map(|c| c.to_account_info());
+        release_sold_asset(
+            &self.mpl_core_program.to_account_info(),
+            &self.asset.to_account_info(),
+            collection.as_ref(),
+            &self.escrow.to_account_info(),
+            &self.buyer.to_account_info(),
+            &self.buyer.to_account_info(),
+            &self.marketplace.to_account_info(),
+            &self.system_program.to_account_info(),
+            signers_seeds,
+            marketplace_seeds,
+        )
     }
 }
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::{Marketplace, VaultPartner, VaultPartnerRegistered, VaultPartnerStatus};
+
+#[derive(Accounts)]
+#[instruction(params: RegisterVaultPartnerParams)]
+pub struct RegisterVaultPartner<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"vault_partner", marketplace.key().as_ref(), params.authority.as_ref()],
+        bump,
+        space = 8 + VaultPartner::INIT_SPACE
+    )]
+    pub vault_partner: Account<'info, VaultPartner>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterVaultPartner<'info> {
+    /// The vaults the partner already custodies are passed as `vaults`, later vaults are added by `create_vault`
+    pub fn register_vault_partner(
+        &mut self,
+        params: RegisterVaultPartnerParams,
+        vaults: &[AccountInfo<'info>],
+        bumps: &RegisterVaultPartnerBumps,
+    ) -> Result<()> {
+        require!(params.name.len() <= 32, MarketplaceError::NameTooLong);
+        require!(!params.name.is_empty(), MarketplaceError::UndefinedName);
+        require!(
+            (2..=3).contains(&params.jurisdiction_code.len())
+                && params
+                    .jurisdiction_code
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase()),
+            MarketplaceError::InvalidJurisdictionCode
+        );
+        require!(
+            params.encryption_key != [0u8; 32],
+            MarketplaceError::InvalidEncryptionKey
+        );
+
+        self.vault_partner.set_inner(VaultPartner {
+            marketplace: self.marketplace.key(),
+            authority: params.authority,
+            status: VaultPartnerStatus::Active,
+            bump: bumps.vault_partner,
+            encryption_key: params.encryption_key,
+            name: params.name,
+            jurisdiction_code: params.jurisdiction_code,
+            vaults: Vec::new(),
+        });
+        self.vault_partner.set_vaults(vaults)?;
+
+        emit!(VaultPartnerRegistered {
+            marketplace: self.marketplace.key(),
+            vault_partner: self.vault_partner.key(),
+            authority: self.vault_partner.authority,
+            name: self.vault_partner.name.clone(),
+            jurisdiction_code: self.vault_partner.jurisdiction_code.clone(),
+            vaults: self.vault_partner.vaults.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RegisterVaultPartnerParams {
+    pub authority: Pubkey,
+    pub encryption_key: [u8; 32],
+    pub name: String,
+    pub jurisdiction_code: String,
+}
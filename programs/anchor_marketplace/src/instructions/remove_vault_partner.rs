@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::{Marketplace, VaultPartner, VaultPartnerRemoved};
+
+#[derive(Accounts)]
+pub struct RemoveVaultPartner<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_partner", marketplace.key().as_ref(), vault_partner.authority.as_ref()],
+        bump = vault_partner.bump,
+        close = admin,
+    )]
+    pub vault_partner: Account<'info, VaultPartner>,
+}
+
+impl<'info> RemoveVaultPartner<'info> {
+    /// Vaults must first be handed to another partner through `set_partner_vaults`
+    pub fn remove_vault_partner(&mut self) -> Result<()> {
+        require!(
+            self.vault_partner.vaults.is_empty(),
+            MarketplaceError::VaultPartnerHasVaults
+        );
+
+        emit!(VaultPartnerRemoved {
+            marketplace: self.marketplace.key(),
+            vault_partner: self.vault_partner.key(),
+            authority: self.vault_partner.authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,285 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    instructions::UpdatePluginV1CpiBuilder,
+    types::{BurnDelegate, FreezeDelegate, Plugin, UpdateAuthority},
+};
+
+use crate::delegate::{delegate_plugin, is_frozen};
+use crate::payment::{payment_account, payment_program, transfer_payment};
+use crate::{
+    CollectionConfig, Marketplace, MarketplaceError, MetalAttributes, PaymentMint, Redemption,
+    RedemptionRequested, RedemptionStatus, Vault, VaultPartner, MAX_ENCRYPTED_SHIPPING_DETAILS_LEN,
+};
+
+#[derive(Accounts)]
+pub struct RequestRedemption<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// An asset minted by this marketplace, directly or into one of its collections
+    #[account(
+        mut,
+        constraint = asset.owner == owner.key() @ MarketplaceError::NotAssetOwner,
+        constraint = match asset.update_authority {
+            // assets minted before the marketplace PDA became update authority are held by the admin
+            UpdateAuthority::Address(authority) => authority == marketplace.key() || authority == marketplace.admin,
+            UpdateAuthority::Collection(collection_key) => collection.is_some() && collection_config.as_ref().is_some_and(|config| config.collection == collection_key),
+            UpdateAuthority::None => false,
+        } @ MarketplaceError::UnknownAssetOrigin,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    /// The collection the asset belongs to, required by MPL Core for collection assets
+    #[account(
+        mut,
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()) @ MarketplaceError::CollectionMismatch,
+    )]
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
+
+    /// Registration of the asset's collection, required when the asset belongs to one
+    #[account(
+        seeds = [b"collection", marketplace.key().as_ref(), collection_config.collection.as_ref()],
+        bump = collection_config.bump,
+    )]
+    pub collection_config: Option<Account<'info, CollectionConfig>>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    /// The vault holding the bar backing this asset
+    #[account(
+        seeds = [b"vault", marketplace.key().as_ref(), vault.vault_id.as_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// The vault partner custodying `vault`, whose key the shipping details are sealed to
+    #[account(
+        seeds = [b"vault_partner", marketplace.key().as_ref(), vault_partner.authority.as_ref()],
+        bump = vault_partner.bump,
+        constraint = vault_partner.is_active() @ MarketplaceError::VaultPartnerSuspended,
+        constraint = vault_partner.custodies(&vault.key()) @ MarketplaceError::VaultNotCustodied,
+    )]
+    pub vault_partner: Account<'info, VaultPartner>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"redemption", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump,
+        space = 8 + Redemption::INIT_SPACE
+    )]
+    pub redemption: Account<'info, Redemption>,
+
+    /// The accepted mint the redemption fee is paid with
+    #[account(
+        seeds = [b"payment_mint", marketplace.key().as_ref(), payment_mint_config.mint.as_ref()],
+        bump = payment_mint_config.bump,
+        constraint = payment_mint_config.enabled @ MarketplaceError::PaymentMintDisabled,
+    )]
+    pub payment_mint_config: Account<'info, PaymentMint>,
+
+    /// CHECK: Feed converting USD into the payment mint, validated against `payment_mint_config`
+    pub payment_price_feed: Option<UncheckedAccount<'info>>,
+
+    /// The SPL mint being paid with, omitted when paying in SOL
+    #[account(
+        address = payment_mint_config.mint @ MarketplaceError::PaymentMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token account escrowing the fee until the bar ships
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = payment_mint,
+        associated_token::authority = redemption,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Spot price feed for the asset's metal, validated against the marketplace oracle config
+    pub price_feed: UncheckedAccount<'info>,
+
+    /// CHECK: MPL Program ID
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> RequestRedemption<'info> {
+    pub fn request_redemption(
+        &mut self,
+        params: RequestRedemptionParams,
+        bumps: &RequestRedemptionBumps,
+    ) -> Result<()> {
+        require!(
+            !params.encrypted_shipping_details.is_empty()
+                && params.encrypted_shipping_details.len() <= MAX_ENCRYPTED_SHIPPING_DETAILS_LEN,
+            MarketplaceError::InvalidShippingDetails
+        );
+        require!(
+            self.payment_mint.is_some() != self.payment_mint_config.is_native(),
+            MarketplaceError::PaymentMintMismatch
+        );
+
+        let attributes = MetalAttributes::from_asset(&self.asset.to_account_info())?;
+        require!(
+            attributes.vault_id == self.vault.vault_id,
+            MarketplaceError::VaultMismatch
+        );
+        require!(
+            attributes.metal == self.vault.metal,
+            MarketplaceError::VaultMetalMismatch
+        );
+
+        // The fee is charged on the value of the metal being shipped
+        let usd_price = attributes.spot_value(
+            &self.marketplace.oracle,
+            Some(&self.price_feed.to_account_info()),
+        )?;
+
+        let payment_price_feed = self
+            .payment_price_feed
+            .as_ref()
+            .map(|feed| feed.to_account_info());
+        let token_price = self.payment_mint_config.amount_from_usd(
+            usd_price,
+            &self.marketplace.oracle,
+            payment_price_feed.as_ref(),
+        )?;
+
+        // Pay half purchase fee to redeem physically
+        let amount_to_transfer_as_fee = self.marketplace.calculate_fee(token_price)?;
+
+        let half_amount = amount_to_transfer_as_fee
+            .checked_div(2)
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+
+        let requested_at = Clock::get()?.unix_timestamp;
+        self.redemption.set_inner(Redemption {
+            marketplace: self.marketplace.key(),
+            owner: self.owner.key(),
+            asset: self.asset.key(),
+            vault: self.vault.key(),
+            payment_mint: self.payment_mint_config.mint,
+            fee: half_amount,
+            status: RedemptionStatus::Requested,
+            requested_at,
+            bump: bumps.redemption,
+            vault_partner: self.vault_partner.key(),
+            encryption_key: self.vault_partner.encryption_key,
+            shipping_commitment: params.shipping_commitment,
+            encrypted_shipping_details: params.encrypted_shipping_details,
+        });
+
+        // Escrow the fee until the vault partner ships the bar
+        let payment_mint = self.payment_mint.as_ref();
+        transfer_payment(
+            payment_mint,
+            payment_account(
+                payment_mint,
+                self.owner.to_account_info(),
+                self.owner_payment_account.as_ref(),
+            )?,
+            payment_account(
+                payment_mint,
+                self.redemption.to_account_info(),
+                self.escrow_payment_account.as_ref(),
+            )?,
+            self.owner.to_account_info(),
+            payment_program(
+                payment_mint,
+                self.token_program.to_account_info(),
+                self.system_program.to_account_info(),
+            ),
+            half_amount,
+            &[],
+        )?;
+
+        let marketplace_id = self.marketplace.id.to_le_bytes();
+        let marketplace_seeds: &[&[&[u8]]] = &[&[
+            b"marketplace",
+            marketplace_id.as_ref(),
+            &[self.marketplace.bump],
+        ]];
+
+        let mpl_core_program = self.mpl_core_program.to_account_info();
+        let asset = self.asset.to_account_info();
+        let collection = self.collection.as_ref().map(|c| c.to_account_info());
+        let owner = self.owner.to_account_info();
+        let system_program = self.system_program.to_account_info();
+
+        // Assets bought on the marketplace are already frozen under it, anything else hands
+        // the marketplace its freeze and burn delegates first
+        if !is_frozen(&asset) {
+            for plugin in [
+                Plugin::BurnDelegate(BurnDelegate {}),
+                Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+            ] {
+                delegate_plugin(
+                    &mpl_core_program,
+                    &asset,
+                    collection.as_ref(),
+                    &owner,
+                    &owner,
+                    &system_program,
+                    plugin,
+                    self.marketplace.key(),
+                    &[],
+                )?;
+            }
+
+            // Freeze the asset so it cannot be moved while the redemption is pending
+            UpdatePluginV1CpiBuilder::new(&mpl_core_program)
+                .asset(&asset)
+                .collection(collection.as_ref())
+                .payer(&owner)
+                .authority(Some(&self.marketplace.to_account_info()))
+                .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: true }))
+                .system_program(&system_program)
+                .invoke_signed(marketplace_seeds)?;
+        }
+
+        emit!(RedemptionRequested {
+            redemption: self.redemption.key(),
+            asset: self.asset.key(),
+            owner: self.owner.key(),
+            vault: self.vault.key(),
+            payment_mint: self.redemption.payment_mint,
+            fee: half_amount,
+            vault_partner: self.vault_partner.key(),
+            shipping_commitment: params.shipping_commitment,
+            timestamp: requested_at,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RequestRedemptionParams {
+    /// Hash of the plaintext shipping details and a salt
+    pub shipping_commitment: [u8; 32],
+    /// Shipping details sealed (x25519) to the vault partner's encryption key
+    pub encrypted_shipping_details: Vec<u8>,
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::{Marketplace, VaultPartner};
+
+#[derive(Accounts)]
+pub struct SetPartnerVaults<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_partner", marketplace.key().as_ref(), vault_partner.authority.as_ref()],
+        bump = vault_partner.bump,
+    )]
+    pub vault_partner: Account<'info, VaultPartner>,
+}
+
+impl<'info> SetPartnerVaults<'info> {
+    /// Replaces the partner's vaults with `vaults`, used when custody moves between partners
+    pub fn set_partner_vaults(&mut self, vaults: &[AccountInfo<'info>]) -> Result<()> {
+        self.vault_partner.set_vaults(vaults)
+    }
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::{Marketplace, SellerApprovalUpdated, SellerProfile};
+
+#[derive(Accounts)]
+pub struct SetSellerApproval<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: The seller being approved or revoked, only used as a seed
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"seller_profile", marketplace.key().as_ref(), seller.key().as_ref()],
+        bump,
+        space = 8 + SellerProfile::INIT_SPACE
+    )]
+    pub seller_profile: Account<'info, SellerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetSellerApproval<'info> {
+    pub fn set_seller_approval(
+        &mut self,
+        params: SetSellerApprovalParams,
+        bumps: &SetSellerApprovalBumps,
+    ) -> Result<()> {
+        if self.seller_profile.seller == Pubkey::default() {
+            self.seller_profile.set_inner(SellerProfile {
+                marketplace: self.marketplace.key(),
+                seller: self.seller.key(),
+                approved: false,
+                listing_count: 0,
+                sale_count: 0,
+                volume: 0,
+                bump: bumps.seller_profile,
+            });
+        }
+        self.seller_profile.approved = params.approved;
+
+        emit!(SellerApprovalUpdated {
+            marketplace: self.marketplace.key(),
+            seller: self.seller.key(),
+            approved: params.approved,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct SetSellerApprovalParams {
+    pub approved: bool,
+}
@@ -0,0 +1,332 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    types::UpdateAuthority,
+};
+
+use crate::delegate::{release_sold_asset, return_listed_asset};
+use crate::error::MarketplaceError;
+use crate::payment::{close_escrow_token_account, pay_sale, payment_account, PaymentSource};
+use crate::{
+    AssetSold, AuctionSettled, Bid, Listing, Marketplace, PaymentMint, SaleRecord, SellerProfile,
+};
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    /// Anyone can settle an ended auction, paying for any accounts it creates
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = seller.key() == listing.seller @ MarketplaceError::SellerMismatch
+    )]
+    pub seller: SystemAccount<'info>,
+
+    /// The highest bidder, required once the auction has bids
+    #[account(
+        mut,
+        constraint = Some(winner.key()) == listing.highest_bidder() @ MarketplaceError::BidMismatch,
+    )]
+    pub winner: Option<SystemAccount<'info>>,
+
+    /// The MPL Core asset being auctioned
+    #[account(
+        mut,
+        constraint = asset.key() == listing.mint @ MarketplaceError::AssetMismatch,
+        constraint = matches!(asset.update_authority, UpdateAuthority::Collection(_)) == collection.is_some() @ MarketplaceError::CollectionMismatch,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+
+    /// The collection that this asset belongs to
+    #[account(
+        mut,
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()) @ MarketplaceError::CollectionMismatch,
+    )]
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
+
+    /// CHECK: The escrow account that currently holds the asset
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = listing.escrow_bump,
+        constraint = asset.owner == escrow.key() @ MarketplaceError::AssetNotInEscrow,
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"listing", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump = listing.bump,
+        close = seller,
+        constraint = listing.is_active @ MarketplaceError::ListingNotActive,
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"seller_profile", marketplace.key().as_ref(), seller.key().as_ref()],
+        bump = seller_profile.bump,
+    )]
+    pub seller_profile: Account<'info, SellerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"sale_record", marketplace.key().as_ref(), asset.key().as_ref()],
+        bump,
+        space = 8 + SaleRecord::INIT_SPACE
+    )]
+    pub sale_record: Account<'info, SaleRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    /// The winning bid, paid out and closed to `winner`
+    #[account(
+        mut,
+        seeds = [b"bid", listing.key().as_ref(), bid.bidder.as_ref()],
+        bump = bid.bump,
+        constraint = Some(bid.bidder) == listing.highest_bidder() @ MarketplaceError::BidMismatch,
+    )]
+    pub bid: Option<Account<'info, Bid>>,
+
+    /// The accepted mint the winning bid was placed in, required once the auction has bids
+    #[account(
+        seeds = [b"payment_mint", marketplace.key().as_ref(), payment_mint_config.mint.as_ref()],
+        bump = payment_mint_config.bump,
+    )]
+    pub payment_mint_config: Option<Account<'info, PaymentMint>>,
+
+    /// CHECK: Feed converting the payment mint into USD for the sale record, validated against `payment_mint_config`
+    pub payment_price_feed: Option<UncheckedAccount<'info>>,
+
+    /// The SPL mint of the winning bid, omitted for SOL bids
+    #[account(mint::token_program = token_program)]
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = bid,
+        associated_token::token_program = token_program,
+    )]
+    pub bid_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = payment_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: MPL Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> SettleAuction<'info> {
+    /// Sells the asset to the highest bidder, or returns it to the seller when nobody bid.
+    /// Creator wallets, or their payment token accounts for SPL bids, are passed as
+    /// `creator_accounts` in the order of the royalties plugin.
+    pub fn settle_auction(
+        &mut self,
+        creator_accounts: &[AccountInfo<'info>],
+        bumps: &SettleAuctionBumps,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= self.listing.auction_end_time()?,
+            MarketplaceError::AuctionNotEnded
+        );
+
+        let amount = match self.listing.highest_bidder() {
+            Some(_) => self.sell_to_winner(creator_accounts, bumps)?,
+            None => {
+                self.return_to_seller()?;
+                0
+            }
+        };
+
+        emit!(AuctionSettled {
+            listing: self.listing.key(),
+            asset: self.asset.key(),
+            seller: self.seller.key(),
+            winner: self.listing.highest_bidder(),
+            amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Pays the winning bid out through the same split as a purchase and delivers the asset,
+    /// returning the amount paid
+    fn sell_to_winner(
+        &mut self,
+        creator_accounts: &[AccountInfo<'info>],
+        bumps: &SettleAuctionBumps,
+    ) -> Result<u64> {
+        let (Some(winner), Some(bid), Some(payment_mint_config)) =
+            (&self.winner, &self.bid, &self.payment_mint_config)
+        else {
+            return err!(MarketplaceError::BidMismatch);
+        };
+        require_keys_eq!(
+            payment_mint_config.mint,
+            bid.payment_mint,
+            MarketplaceError::PaymentMintMismatch
+        );
+        require!(
+            match &self.payment_mint {
+                Some(mint) => mint.key() == payment_mint_config.mint,
+                None => payment_mint_config.is_native(),
+            },
+            MarketplaceError::PaymentMintMismatch
+        );
+
+        let listing_key = self.listing.key();
+        let bid_seeds: &[&[&[u8]]] = &[&[
+            b"bid",
+            listing_key.as_ref(),
+            bid.bidder.as_ref(),
+            &[bid.bump],
+        ]];
+
+        let payment_mint = self.payment_mint.as_ref();
+        let source = PaymentSource::Escrow {
+            escrow: bid.to_account_info(),
+            token_account: self.bid_payment_account.as_ref(),
+            token_program: self.token_program.to_account_info(),
+            signer_seeds: bid_seeds,
+        };
+
+        let collection = self.collection.as_ref().map(|c| c.to_account_info());
+        let split = pay_sale(
+            &source,
+            payment_mint,
+            &self.marketplace,
+            &self.asset.to_account_info(),
+            collection.as_ref(),
+            creator_accounts,
+            payment_account(
+                payment_mint,
+                self.treasury.to_account_info(),
+                self.treasury_payment_account.as_ref(),
+            )?,
+            payment_account(
+                payment_mint,
+                self.seller.to_account_info(),
+                self.seller_payment_account.as_ref(),
+            )?,
+            bid.amount,
+        )?;
+
+        // The emptied escrow's rent goes back to the winner
+        close_escrow_token_account(
+            bid.to_account_info(),
+            self.bid_payment_account.as_ref(),
+            winner.to_account_info(),
+            self.token_program.to_account_info(),
+            bid_seeds,
+        )?;
+        bid.close(winner.to_account_info())?;
+
+        let escrow_seeds: &[&[&[u8]]] =
+            &[&[b"escrow", listing_key.as_ref(), &[self.listing.escrow_bump]]];
+        let marketplace_id = self.marketplace.id.to_le_bytes();
+        let marketplace_seeds: &[&[&[u8]]] = &[&[
+            b"marketplace",
+            marketplace_id.as_ref(),
+            &[self.marketplace.bump],
+        ]];
+        release_sold_asset(
+            &self.mpl_core_program.to_account_info(),
+            &self.asset.to_account_info(),
+            collection.as_ref(),
+            &self.escrow.to_account_info(),
+            &winner.to_account_info(),
+            &self.payer.to_account_info(),
+            &self.marketplace.to_account_info(),
+            &self.system_program.to_account_info(),
+            escrow_seeds,
+            marketplace_seeds,
+        )?;
+
+        let payment_price_feed = self
+            .payment_price_feed
+            .as_ref()
+            .map(|feed| feed.to_account_info());
+        let usd_price = payment_mint_config.usd_from_amount(
+            bid.amount,
+            &self.marketplace.oracle,
+            payment_price_feed.as_ref(),
+        )?;
+
+        let amount = bid.amount;
+        let clock = Clock::get()?;
+        let sale = AssetSold {
+            marketplace: self.marketplace.key(),
+            asset: self.asset.key(),
+            seller: self.seller.key(),
+            buyer: winner.key(),
+            price: usd_price,
+            payment_mint: bid.payment_mint,
+            amount,
+            fee: split.fee,
+            royalty: split.royalty,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        };
+
+        self.seller_profile.record_sale(usd_price)?;
+        self.sale_record.record(sale, bumps.sale_record)?;
+
+        Ok(amount)
+    }
+
+    fn return_to_seller(&self) -> Result<()> {
+        let listing_key = self.listing.key();
+        let escrow_seeds: &[&[&[u8]]] =
+            &[&[b"escrow", listing_key.as_ref(), &[self.listing.escrow_bump]]];
+
+        let collection = self.collection.as_ref().map(|c| c.to_account_info());
+        return_listed_asset(
+            &self.mpl_core_program.to_account_info(),
+            &self.asset.to_account_info(),
+            collection.as_ref(),
+            &self.escrow.to_account_info(),
+            &self.seller.to_account_info(),
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+            escrow_seeds,
+        )
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::{Marketplace, VaultPartner, VaultPartnerStatus, VaultPartnerSuspended};
+
+#[derive(Accounts)]
+pub struct SuspendVaultPartner<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_partner", marketplace.key().as_ref(), vault_partner.authority.as_ref()],
+        bump = vault_partner.bump,
+    )]
+    pub vault_partner: Account<'info, VaultPartner>,
+}
+
+impl<'info> SuspendVaultPartner<'info> {
+    pub fn suspend_vault_partner(&mut self, params: SuspendVaultPartnerParams) -> Result<()> {
+        self.vault_partner.status = if params.suspended {
+            VaultPartnerStatus::Suspended
+        } else {
+            VaultPartnerStatus::Active
+        };
+
+        emit!(VaultPartnerSuspended {
+            marketplace: self.marketplace.key(),
+            vault_partner: self.vault_partner.key(),
+            suspended: params.suspended,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct SuspendVaultPartnerParams {
+    pub suspended: bool,
+}
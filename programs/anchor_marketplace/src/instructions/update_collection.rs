@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use mpl_core::{accounts::BaseCollectionV1, instructions::UpdateCollectionV1CpiBuilder};
+
+use crate::error::MarketplaceError;
+use crate::{CollectionConfig, Marketplace};
+
+#[derive(Accounts)]
+pub struct UpdateCollection<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub collection: Account<'info, BaseCollectionV1>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"collection", marketplace.key().as_ref(), collection.key().as_ref()],
+        bump = collection_config.bump,
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// CHECK: MPL Core program
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateCollection<'info> {
+    pub fn update_collection(&mut self, params: UpdateCollectionParams) -> Result<()> {
+        if params.name.is_some() || params.uri.is_some() {
+            let marketplace_id = self.marketplace.id.to_le_bytes();
+            let marketplace_seeds: &[&[&[u8]]] = &[&[
+                b"marketplace",
+                marketplace_id.as_ref(),
+                &[self.marketplace.bump],
+            ]];
+
+            let mpl_core_program = self.mpl_core_program.to_account_info();
+            let mut builder = UpdateCollectionV1CpiBuilder::new(&mpl_core_program);
+            let collection = self.collection.to_account_info();
+            let admin = self.admin.to_account_info();
+            let marketplace = self.marketplace.to_account_info();
+            let system_program = self.system_program.to_account_info();
+            builder
+                .collection(&collection)
+                .payer(&admin)
+                .authority(Some(&marketplace))
+                .system_program(&system_program);
+
+            if let Some(name) = params.name {
+                require!(!name.is_empty(), MarketplaceError::UndefinedName);
+                builder.new_name(name);
+            }
+            if let Some(uri) = params.uri {
+                builder.new_uri(uri);
+            }
+
+            builder.invoke_signed(marketplace_seeds)?;
+        }
+
+        if let Some(tradable) = params.tradable {
+            self.collection_config.tradable = tradable;
+        }
+        Ok(())
+    }
+}
+
+/// Fields left as `None` keep their current value
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct UpdateCollectionParams {
+    pub name: Option<String>,
+    pub uri: Option<String>,
+    pub tradable: Option<bool>,
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use mpl_core::accounts::BaseAssetV1;
+
+use crate::error::MarketplaceError;
+use crate::{Listing, ListingKind, ListingKindParams, ListingUpdated, Marketplace};
+
+#[derive(Accounts)]
+pub struct UpdateListing<'info> {
+    pub seller: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"listing", marketplace.key().as_ref(), listing.mint.as_ref()],
+        bump = listing.bump,
+        constraint = listing.is_active @ MarketplaceError::ListingNotActive,
+        constraint = seller.key() == listing.seller @ MarketplaceError::SellerMismatch,
+        constraint = !listing.is_auction() @ MarketplaceError::AuctionListing,
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// The listed asset, read for its metal attributes when switching to spot pricing
+    #[account(
+        address = listing.mint @ MarketplaceError::AssetMismatch,
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+}
+
+impl<'info> UpdateListing<'info> {
+    pub fn update_listing(&mut self, params: UpdateListingParams) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let old_price = self.listing.price;
+        let old_token_id = self.listing.token_id;
+
+        if let Some(kind) = params.kind {
+            let kind = kind.into_kind(&self.asset.to_account_info())?;
+            // Bids are escrowed against an auction's terms, so auctions are only created by listing
+            require!(
+                !matches!(kind, ListingKind::Auction { .. }),
+                MarketplaceError::AuctionListing
+            );
+            kind.validate(now)?;
+            self.listing.kind = kind;
+        }
+
+        // Spot and Dutch listings price themselves from their kind
+        if let Some(price) = params.price {
+            require!(
+                self.listing.kind == ListingKind::Fixed,
+                MarketplaceError::PriceNotFixed
+            );
+            self.listing.price = price;
+        }
+        if let Some(token_id) = params.token_id {
+            self.listing.token_id = token_id;
+        }
+
+        emit!(ListingUpdated {
+            listing: self.listing.key(),
+            asset: self.listing.mint,
+            seller: self.seller.key(),
+            old_price,
+            new_price: self.listing.price,
+            old_token_id,
+            new_token_id: self.listing.token_id,
+            kind: self.listing.kind.clone(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+/// Fields left as `None` keep their current value
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct UpdateListingParams {
+    /// Only accepted for fixed price listings
+    pub price: Option<u64>,
+    pub token_id: Option<u16>,
+    /// Reprices the listing, e.g. a new spot premium or Dutch schedule; auctions are not accepted
+    pub kind: Option<ListingKindParams>,
+}
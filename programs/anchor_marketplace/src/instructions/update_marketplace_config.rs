@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::{Marketplace, MarketplaceConfigUpdated, SellerPermission};
+
+#[derive(Accounts)]
+pub struct UpdateMarketplaceConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+}
+
+impl<'info> UpdateMarketplaceConfig<'info> {
+    pub fn update_marketplace_config(
+        &mut self,
+        params: UpdateMarketplaceConfigParams,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let marketplace = &mut self.marketplace;
+
+        if let Some(name) = params.name {
+            require!(name.len() <= 32, MarketplaceError::NameTooLong);
+            require!(!name.is_empty(), MarketplaceError::UndefinedName);
+            marketplace.name = name;
+        }
+
+        // Set the fee first so an increase in the same call waits for the timelock already in force
+        if let Some(fee_bps) = params.fee_bps {
+            marketplace.set_fee_bps(fee_bps, now)?;
+        }
+
+        if let Some(fee_timelock) = params.fee_timelock {
+            marketplace.set_fee_timelock(fee_timelock, now)?;
+        }
+
+        if let Some(seller_permission) = params.seller_permission {
+            marketplace.seller_permission = seller_permission;
+        }
+
+        if let Some(expiry_bounty) = params.expiry_bounty {
+            marketplace.expiry_bounty = expiry_bounty;
+        }
+
+        emit!(MarketplaceConfigUpdated {
+            marketplace: marketplace.key(),
+            name: marketplace.name.clone(),
+            fee_bps: marketplace.fee_bps,
+            fee_timelock: marketplace.fee_timelock,
+            pending_fee: marketplace.pending_fee,
+            pending_fee_timelock: marketplace.pending_fee_timelock,
+            seller_permission: marketplace.seller_permission,
+            expiry_bounty: marketplace.expiry_bounty,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+/// Fields left as `None` keep their current value
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct UpdateMarketplaceConfigParams {
+    pub name: Option<String>,
+    pub fee_bps: Option<u16>,
+    pub fee_timelock: Option<u64>,
+    pub seller_permission: Option<SellerPermission>,
+    /// Lamports, capped at the rent of the expired listing
+    pub expiry_bounty: Option<u64>,
+}
@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
 use mpl_core::{
     accounts::{BaseAssetV1, BaseCollectionV1},
-    instructions::{CreateV1CpiBuilder, UpdateV1CpiBuilder},
-    types::{DataState, Plugin, PluginAuthority, UpdateAuthority},
+    instructions::{UpdatePluginV1CpiBuilder, UpdateV1CpiBuilder},
+    types::{Plugin, UpdateAuthority},
 };
 
-use crate::MarketplaceError;
+use crate::{AssetMetadataUpdated, Marketplace, MarketplaceError, MetalAttributes};
 
 #[derive(Accounts)]
 pub struct UpdateNFTMetadata<'info> {
@@ -15,13 +15,28 @@ pub struct UpdateNFTMetadata<'info> {
     /// The MPL Core asset (NFT) to be updated
     #[account(
         mut,
-        constraint = asset.update_authority == UpdateAuthority::Address(authority.key()) @ MarketplaceError::NotUpdateAuthority,
+        constraint = match asset.update_authority {
+            UpdateAuthority::Address(update_authority) => update_authority == marketplace.key(),
+            UpdateAuthority::Collection(_) => collection.is_some(),
+            UpdateAuthority::None => false,
+        } @ MarketplaceError::NotUpdateAuthority,
     )]
     pub asset: Account<'info, BaseAssetV1>,
 
     /// The collection that this asset belongs to (optional)
+    #[account(
+        constraint = asset.update_authority == UpdateAuthority::Collection(collection.key()) @ MarketplaceError::CollectionMismatch,
+        constraint = collection.update_authority == marketplace.key() @ MarketplaceError::NotUpdateAuthority,
+    )]
     pub collection: Option<Account<'info, BaseCollectionV1>>,
 
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == authority.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
     /// CHECK: MPL Core program
     #[account(address = mpl_core::ID)]
     pub mpl_core_program: UncheckedAccount<'info>,
@@ -34,31 +49,89 @@ impl<'info> UpdateNFTMetadata<'info> {
         let authority = self.authority.to_account_info();
         let asset = self.asset.to_account_info();
         let system_prog = self.system_program.to_account_info();
+        let collection = self.collection.as_ref().map(|c| c.to_account_info());
+        let marketplace = self.marketplace.to_account_info();
+
+        let marketplace_id = self.marketplace.id.to_le_bytes();
+        let marketplace_seeds: &[&[&[u8]]] = &[&[
+            b"marketplace",
+            marketplace_id.as_ref(),
+            &[self.marketplace.bump],
+        ]];
+
+        let previous_name = params.name.as_ref().map(|_| self.asset.name.clone());
+        let previous_uri = params.uri.as_ref().map(|_| self.asset.uri.clone());
+
+        // Only fields that were passed are sent, omitted ones keep their current value
+        if params.name.is_some() || params.uri.is_some() {
+            let mut builder = UpdateV1CpiBuilder::new(&mpl_program);
+
+            // The admin is checked above, the marketplace PDA signs as update authority
+            builder
+                .asset(&asset)
+                .collection(collection.as_ref())
+                .payer(&authority)
+                .authority(Some(&marketplace))
+                .system_program(&system_prog);
+
+            if let Some(name) = params.name.clone() {
+                builder.new_name(name);
+            }
+            if let Some(uri) = params.uri.clone() {
+                builder.new_uri(uri);
+            }
+
+            builder.invoke_signed(marketplace_seeds)?;
+        }
+
+        let previous_attributes = match &params.attributes {
+            Some(attributes) => {
+                attributes.validate()?;
+
+                let previous = MetalAttributes::from_asset(&asset)?;
+                // Metal, weight and vault are accounted for in vault inventory
+                require!(
+                    attributes.metal == previous.metal
+                        && attributes.weight_mg == previous.weight_mg
+                        && attributes.vault_id == previous.vault_id,
+                    MarketplaceError::MetalAttributesLocked
+                );
 
-        let mut builder = UpdateV1CpiBuilder::new(&mpl_program);
+                UpdatePluginV1CpiBuilder::new(&mpl_program)
+                    .asset(&asset)
+                    .collection(collection.as_ref())
+                    .payer(&authority)
+                    .authority(Some(&marketplace))
+                    .plugin(Plugin::Attributes(attributes.to_attributes()))
+                    .system_program(&system_prog)
+                    .invoke_signed(marketplace_seeds)?;
 
-        builder
-            .asset(&asset)
-            .payer(&authority)
-            .authority(Some(&authority))
-            .system_program(&system_prog)
-            .new_name(match params.name {
-                Some(name_provided) => name_provided,
-                None => String::new(),
-            })
-            .new_uri(match params.uri {
-                Some(uri_provided) => uri_provided,
-                None => String::new(),
-            });
+                Some(previous)
+            }
+            None => None,
+        };
 
-        builder.invoke()?;
+        emit!(AssetMetadataUpdated {
+            asset: self.asset.key(),
+            authority: self.authority.key(),
+            previous_name,
+            name: params.name,
+            previous_uri,
+            uri: params.uri,
+            previous_attributes,
+            attributes: params.attributes,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         Ok(())
     }
 }
 
+/// Fields left as `None` keep their current value
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct UpdateNFTParams {
     pub name: Option<String>,
     pub uri: Option<String>,
+    /// Assay corrections, metal, weight and vault must stay the same
+    pub attributes: Option<MetalAttributes>,
 }
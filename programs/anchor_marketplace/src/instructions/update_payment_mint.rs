@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::{Marketplace, PaymentMint, PaymentMintParams};
+
+#[derive(Accounts)]
+pub struct UpdatePaymentMint<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"payment_mint", marketplace.key().as_ref(), payment_mint.mint.as_ref()],
+        bump = payment_mint.bump,
+    )]
+    pub payment_mint: Account<'info, PaymentMint>,
+}
+
+impl<'info> UpdatePaymentMint<'info> {
+    pub fn update_payment_mint(&mut self, params: PaymentMintParams) -> Result<()> {
+        self.payment_mint.enabled = params.enabled;
+        self.payment_mint.price_feed = params.price_feed;
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::{Marketplace, Vault, VaultPartner};
+
+#[derive(Accounts)]
+pub struct UpdateVault<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", marketplace.key().as_ref(), vault.vault_id.as_bytes()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// The vault partner custodying `vault`, attesting to the inventory change
+    pub vault_partner_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault_partner", marketplace.key().as_ref(), vault_partner_authority.key().as_ref()],
+        bump = vault_partner.bump,
+        constraint = vault_partner.is_active() @ MarketplaceError::VaultPartnerSuspended,
+        constraint = vault_partner.custodies(&vault.key()) @ MarketplaceError::VaultNotCustodied,
+    )]
+    pub vault_partner: Account<'info, VaultPartner>,
+}
+
+impl<'info> UpdateVault<'info> {
+    pub fn update_vault(&mut self, params: UpdateVaultParams) -> Result<()> {
+        let committed_mg = self
+            .vault
+            .minted_mg
+            .checked_add(params.reserved_mg)
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+        require!(
+            params.total_mg >= committed_mg,
+            MarketplaceError::InvalidVaultInventory
+        );
+
+        self.vault.total_mg = params.total_mg;
+        self.vault.reserved_mg = params.reserved_mg;
+        Ok(())
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct UpdateVaultParams {
+    pub total_mg: u64,
+    pub reserved_mg: u64,
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::MarketplaceError;
+use crate::payment::{payment_account, payment_program, transfer_payment};
+use crate::{Marketplace, TreasuryWithdrawn};
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"marketplace", marketplace.id.to_le_bytes().as_ref()],
+        bump = marketplace.bump,
+        constraint = marketplace.admin == admin.key() @ MarketplaceError::UnauthorizedAdmin,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    /// Wallet receiving SOL withdrawals
+    #[account(mut)]
+    pub destination: SystemAccount<'info>,
+
+    /// The SPL mint being withdrawn, omitted when withdrawing SOL
+    #[account(mint::token_program = token_program)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = destination,
+        token::token_program = token_program,
+    )]
+    pub destination_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawTreasury<'info> {
+    pub fn withdraw_treasury(&mut self, amount: u64) -> Result<()> {
+        let mint = self.mint.as_ref();
+
+        // Keep the treasury rent exempt, token accounts can be drained entirely
+        let available = match &self.treasury_payment_account {
+            Some(treasury_payment_account) if mint.is_some() => treasury_payment_account.amount,
+            _ => self
+                .treasury
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(self.treasury.data_len())),
+        };
+        require!(
+            amount <= available,
+            MarketplaceError::InsufficientTreasuryBalance
+        );
+
+        let marketplace_key = self.marketplace.key();
+        let treasury_seeds: &[&[&[u8]]] = &[&[
+            b"treasury",
+            marketplace_key.as_ref(),
+            &[self.marketplace.treasury_bump],
+        ]];
+
+        transfer_payment(
+            mint,
+            payment_account(
+                mint,
+                self.treasury.to_account_info(),
+                self.treasury_payment_account.as_ref(),
+            )?,
+            payment_account(
+                mint,
+                self.destination.to_account_info(),
+                self.destination_payment_account.as_ref(),
+            )?,
+            self.treasury.to_account_info(),
+            payment_program(
+                mint,
+                self.token_program.to_account_info(),
+                self.system_program.to_account_info(),
+            ),
+            amount,
+            treasury_seeds,
+        )?;
+
+        emit!(TreasuryWithdrawn {
+            marketplace: marketplace_key,
+            destination: self.destination.key(),
+            mint: mint.map_or(native_mint::ID, |mint| mint.key()),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -1,11 +1,15 @@
 pub mod constants;
+pub mod delegate;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod payment;
 pub mod state;
 
 use anchor_lang::prelude::*;
 
 pub use constants::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -16,12 +20,95 @@ pub mod anchor_marketplace {
     use super::*;
 
     pub fn initialize(ctx: Context<InitializeMarketplace>, params: InitializeParams) -> Result<()> {
-        ctx.accounts
-            .handle(params.name, params.fee_bps, &ctx.bumps)?;
+        ctx.accounts.handle(params, &ctx.bumps)?;
 
         Ok(())
     }
 
+    pub fn update_marketplace_config(
+        ctx: Context<UpdateMarketplaceConfig>,
+        params: UpdateMarketplaceConfigParams,
+    ) -> Result<()> {
+        ctx.accounts.update_marketplace_config(params)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.propose_admin(new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn add_payment_mint(ctx: Context<AddPaymentMint>, params: PaymentMintParams) -> Result<()> {
+        ctx.accounts.add_payment_mint(params, &ctx.bumps)
+    }
+
+    pub fn update_payment_mint(
+        ctx: Context<UpdatePaymentMint>,
+        params: PaymentMintParams,
+    ) -> Result<()> {
+        ctx.accounts.update_payment_mint(params)
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_treasury(amount)
+    }
+
+    pub fn register_vault_partner<'info>(
+        ctx: Context<'_, '_, '_, 'info, RegisterVaultPartner<'info>>,
+        params: RegisterVaultPartnerParams,
+    ) -> Result<()> {
+        ctx.accounts
+            .register_vault_partner(params, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn suspend_vault_partner(
+        ctx: Context<SuspendVaultPartner>,
+        params: SuspendVaultPartnerParams,
+    ) -> Result<()> {
+        ctx.accounts.suspend_vault_partner(params)
+    }
+
+    pub fn remove_vault_partner(ctx: Context<RemoveVaultPartner>) -> Result<()> {
+        ctx.accounts.remove_vault_partner()
+    }
+
+    pub fn set_partner_vaults<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetPartnerVaults<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.set_partner_vaults(ctx.remaining_accounts)
+    }
+
+    pub fn set_seller_approval(
+        ctx: Context<SetSellerApproval>,
+        params: SetSellerApprovalParams,
+    ) -> Result<()> {
+        ctx.accounts.set_seller_approval(params, &ctx.bumps)
+    }
+
+    pub fn create_vault(ctx: Context<CreateVault>, params: CreateVaultParams) -> Result<()> {
+        ctx.accounts.create_vault(params, &ctx.bumps)
+    }
+
+    pub fn update_vault(ctx: Context<UpdateVault>, params: UpdateVaultParams) -> Result<()> {
+        ctx.accounts.update_vault(params)
+    }
+
+    pub fn create_collection(
+        ctx: Context<CreateCollection>,
+        params: CreateCollectionParams,
+    ) -> Result<()> {
+        ctx.accounts.create_collection(params, &ctx.bumps)
+    }
+
+    pub fn update_collection(
+        ctx: Context<UpdateCollection>,
+        params: UpdateCollectionParams,
+    ) -> Result<()> {
+        ctx.accounts.update_collection(params)
+    }
+
     pub fn create_nft(ctx: Context<CreateNFT>, params: CreateNFTParams) -> Result<()> {
         ctx.accounts.create_nft(params)
     }
@@ -36,19 +123,99 @@ pub mod anchor_marketplace {
         Ok(())
     }
 
-    pub fn purchase_nft(ctx: Context<Purchase>) -> Result<()> {
-        ctx.accounts.make_payment()?;
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        ctx.accounts.cancel_listing()
+    }
+
+    pub fn expire_listing(ctx: Context<ExpireListing>) -> Result<()> {
+        ctx.accounts.expire_listing()
+    }
+
+    pub fn update_listing(ctx: Context<UpdateListing>, params: UpdateListingParams) -> Result<()> {
+        ctx.accounts.update_listing(params)
+    }
+
+    pub fn purchase_nft<'info>(ctx: Context<'_, '_, '_, 'info, Purchase<'info>>) -> Result<()> {
+        ctx.accounts
+            .make_payment(ctx.remaining_accounts, &ctx.bumps)?;
         ctx.accounts.transfer_nft()?;
         Ok(())
     }
 
-    pub fn redeem_asset(ctx: Context<RedeemNFT>) -> Result<()> {
-        ctx.accounts.redeem_nft()
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        ctx.accounts.place_bid(amount, &ctx.bumps)
+    }
+
+    pub fn settle_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleAuction<'info>>,
+    ) -> Result<()> {
+        ctx.accounts
+            .settle_auction(ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn make_offer(ctx: Context<MakeOffer>, params: MakeOfferParams) -> Result<()> {
+        ctx.accounts.make_offer(params, &ctx.bumps)
+    }
+
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        ctx.accounts.cancel_offer()
+    }
+
+    pub fn accept_offer<'info>(ctx: Context<'_, '_, '_, 'info, AcceptOffer<'info>>) -> Result<()> {
+        ctx.accounts
+            .accept_offer(ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn make_collection_offer(
+        ctx: Context<MakeCollectionOffer>,
+        params: MakeCollectionOfferParams,
+    ) -> Result<()> {
+        ctx.accounts.make_collection_offer(params, &ctx.bumps)
+    }
+
+    pub fn cancel_collection_offer(ctx: Context<CancelCollectionOffer>) -> Result<()> {
+        ctx.accounts.cancel_collection_offer()
+    }
+
+    pub fn fill_collection_offer<'info>(
+        ctx: Context<'_, '_, '_, 'info, FillCollectionOffer<'info>>,
+    ) -> Result<()> {
+        ctx.accounts
+            .fill_collection_offer(ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn request_redemption(
+        ctx: Context<RequestRedemption>,
+        params: RequestRedemptionParams,
+    ) -> Result<()> {
+        ctx.accounts.request_redemption(params, &ctx.bumps)
+    }
+
+    pub fn accept_redemption(ctx: Context<AcceptRedemption>) -> Result<()> {
+        ctx.accounts.accept_redemption()
+    }
+
+    pub fn reject_redemption(ctx: Context<RejectRedemption>) -> Result<()> {
+        ctx.accounts.reject_redemption()
+    }
+
+    pub fn cancel_redemption(ctx: Context<CancelRedemption>) -> Result<()> {
+        ctx.accounts.cancel_redemption()
+    }
+
+    pub fn confirm_shipment(ctx: Context<ConfirmShipment>) -> Result<()> {
+        ctx.accounts.confirm_shipment()
     }
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, PartialEq)]
 pub struct InitializeParams {
+    /// Seeds the marketplace PDA
+    id: u64,
     name: String,
     fee_bps: u16,
+    /// Seconds a fee increase waits before it applies
+    fee_timelock: u64,
+    oracle: OracleConfig,
+    seller_permission: SellerPermission,
 }
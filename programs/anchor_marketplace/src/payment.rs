@@ -0,0 +1,300 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TransferChecked,
+};
+
+use crate::error::MarketplaceError;
+use crate::{fetch_royalties, Marketplace};
+
+/// Moves `amount` in native SOL when no mint is given, otherwise in the given SPL token.
+/// `program` is the system program for SOL and the token program for SPL tokens.
+pub fn transfer_payment<'info>(
+    mint: Option<&InterfaceAccount<'info, Mint>>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    program: AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    match mint {
+        Some(mint) => {
+            let cpi_accounts = TransferChecked {
+                from,
+                mint: mint.to_account_info(),
+                to,
+                authority,
+            };
+
+            transfer_checked(
+                CpiContext::new_with_signer(program, cpi_accounts, signer_seeds),
+                amount,
+                mint.decimals,
+            )
+        }
+        None => {
+            let cpi_accounts = Transfer { from, to };
+
+            transfer(
+                CpiContext::new_with_signer(program, cpi_accounts, signer_seeds),
+                amount,
+            )
+        }
+    }
+}
+
+/// Picks the program funds move through: the system program for SOL, the token program for SPL tokens
+pub fn payment_program<'info>(
+    mint: Option<&InterfaceAccount<'info, Mint>>,
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> AccountInfo<'info> {
+    match mint {
+        Some(_) => token_program,
+        None => system_program,
+    }
+}
+
+/// Picks the account funds move through: the wallet itself for SOL, its token account for SPL tokens
+pub fn payment_account<'info>(
+    mint: Option<&InterfaceAccount<'info, Mint>>,
+    wallet: AccountInfo<'info>,
+    token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+) -> Result<AccountInfo<'info>> {
+    match mint {
+        Some(_) => token_account
+            .map(|token_account| token_account.to_account_info())
+            .ok_or_else(|| error!(MarketplaceError::PaymentAccountMissing)),
+        None => Ok(wallet),
+    }
+}
+
+/// Pays `amount` out of funds escrowed by a program owned account: its own lamports for SOL,
+/// or the token account it is the authority of for SPL tokens
+pub fn release_escrowed_payment<'info>(
+    mint: Option<&InterfaceAccount<'info, Mint>>,
+    escrow: AccountInfo<'info>,
+    escrow_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    to: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    match mint {
+        Some(_) => transfer_payment(
+            mint,
+            payment_account(mint, escrow.clone(), escrow_token_account)?,
+            to,
+            escrow,
+            token_program,
+            amount,
+            signer_seeds,
+        ),
+        None => {
+            escrow.sub_lamports(amount)?;
+            to.add_lamports(amount)?;
+            Ok(())
+        }
+    }
+}
+
+/// Closes an escrow token account, returning its rent to `destination`
+pub fn close_escrow_token_account<'info>(
+    escrow: AccountInfo<'info>,
+    escrow_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    match escrow_token_account {
+        Some(escrow_token_account) => close_account(CpiContext::new_with_signer(
+            token_program,
+            CloseAccount {
+                account: escrow_token_account.to_account_info(),
+                destination,
+                authority: escrow,
+            },
+            signer_seeds,
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Checks that `account` receives payments for `wallet`: the wallet itself for SOL,
+/// otherwise a token account of `mint` owned by the wallet
+pub fn validate_payment_recipient(
+    mint: Option<&InterfaceAccount<Mint>>,
+    wallet: &Pubkey,
+    account: &AccountInfo,
+) -> Result<()> {
+    require!(
+        account.is_writable,
+        MarketplaceError::PaymentRecipientMismatch
+    );
+
+    match mint {
+        Some(mint) => {
+            require_keys_eq!(
+                *account.owner,
+                *mint.to_account_info().owner,
+                MarketplaceError::PaymentRecipientMismatch
+            );
+            let token_account =
+                TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+            require_keys_eq!(
+                token_account.owner,
+                *wallet,
+                MarketplaceError::PaymentRecipientMismatch
+            );
+            require_keys_eq!(
+                token_account.mint,
+                mint.key(),
+                MarketplaceError::PaymentRecipientMismatch
+            );
+        }
+        None => require_keys_eq!(
+            account.key(),
+            *wallet,
+            MarketplaceError::PaymentRecipientMismatch
+        ),
+    }
+    Ok(())
+}
+
+/// Where the funds for a sale are paid out of
+pub enum PaymentSource<'a, 'info> {
+    /// A buyer paying from their own wallet, or its token account for SPL tokens
+    Wallet {
+        wallet: AccountInfo<'info>,
+        account: AccountInfo<'info>,
+        program: AccountInfo<'info>,
+    },
+    /// Funds held by a program owned escrow, see `release_escrowed_payment`
+    Escrow {
+        escrow: AccountInfo<'info>,
+        token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+        token_program: AccountInfo<'info>,
+        signer_seeds: &'a [&'a [&'a [u8]]],
+    },
+}
+
+impl<'a, 'info> PaymentSource<'a, 'info> {
+    pub fn pay(
+        &self,
+        mint: Option<&InterfaceAccount<'info, Mint>>,
+        to: AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        match self {
+            PaymentSource::Wallet {
+                wallet,
+                account,
+                program,
+            } => transfer_payment(
+                mint,
+                account.clone(),
+                to,
+                wallet.clone(),
+                program.clone(),
+                amount,
+                &[],
+            ),
+            PaymentSource::Escrow {
+                escrow,
+                token_account,
+                token_program,
+                signer_seeds,
+            } => release_escrowed_payment(
+                mint,
+                escrow.clone(),
+                *token_account,
+                to,
+                token_program.clone(),
+                amount,
+                signer_seeds,
+            ),
+        }
+    }
+}
+
+/// What a sale paid out besides the seller's proceeds
+pub struct SaleSplit {
+    pub fee: u64,
+    pub royalty: u64,
+}
+
+/// Splits `amount` paid for `asset`: the marketplace fee goes to the treasury, creator royalties
+/// come out of the seller's proceeds, and the rest goes to the seller.
+/// Creator wallets, or their payment token accounts when paying in SPL tokens, are passed
+/// as `creator_accounts` in the order of the royalties plugin.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_sale<'info>(
+    source: &PaymentSource<'_, 'info>,
+    mint: Option<&InterfaceAccount<'info, Mint>>,
+    marketplace: &Marketplace,
+    asset: &AccountInfo<'info>,
+    collection: Option<&AccountInfo<'info>>,
+    creator_accounts: &[AccountInfo<'info>],
+    treasury_account: AccountInfo<'info>,
+    seller_account: AccountInfo<'info>,
+    amount: u64,
+) -> Result<SaleSplit> {
+    let fee = marketplace.calculate_fee(amount)?;
+    let royalty = pay_royalties(source, mint, asset, collection, creator_accounts, amount)?;
+
+    // Royalty rates are set per asset, so together with the fee they can exceed the whole price
+    let seller_amount = amount
+        .checked_sub(fee)
+        .and_then(|amount| amount.checked_sub(royalty))
+        .ok_or_else(|| error!(MarketplaceError::FeesExceedPrice))?;
+
+    source.pay(mint, treasury_account, fee)?;
+    source.pay(mint, seller_account, seller_amount)?;
+
+    Ok(SaleSplit { fee, royalty })
+}
+
+/// Pays every creator in the asset's (or its collection's) royalties plugin their share of
+/// `amount`, returning the total paid
+fn pay_royalties<'info>(
+    source: &PaymentSource<'_, 'info>,
+    mint: Option<&InterfaceAccount<'info, Mint>>,
+    asset: &AccountInfo<'info>,
+    collection: Option<&AccountInfo<'info>>,
+    creator_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<u64> {
+    let Some(royalties) = fetch_royalties(asset, collection) else {
+        return Ok(0);
+    };
+    require!(
+        creator_accounts.len() >= royalties.creators.len(),
+        MarketplaceError::RoyaltyRecipientMismatch
+    );
+
+    let royalty = (amount as u128)
+        .checked_mul(royalties.basis_points as u128)
+        .ok_or(MarketplaceError::MathOverflowError)?
+        / 10_000;
+
+    let mut paid: u64 = 0;
+    for (creator, creator_account) in royalties.creators.iter().zip(creator_accounts) {
+        validate_payment_recipient(mint, &creator.address, creator_account)
+            .map_err(|_| error!(MarketplaceError::RoyaltyRecipientMismatch))?;
+
+        let share = u64::try_from(royalty * creator.percentage as u128 / 100)
+            .map_err(|_| error!(MarketplaceError::MathOverflowError))?;
+        source.pay(mint, creator_account.clone(), share)?;
+
+        paid = paid
+            .checked_add(share)
+            .ok_or(MarketplaceError::MathOverflowError)?;
+    }
+
+    Ok(paid)
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// The highest bid on an auction listing, closed back to the bidder once outbid
+#[account]
+#[derive(InitSpace)]
+pub struct Bid {
+    pub listing: Pubkey,
+    pub bidder: Pubkey,
+    /// Amount escrowed in `payment_mint` units
+    pub amount: u64,
+    /// Mint the bid is escrowed in, the wrapped SOL mint for native SOL which is escrowed in this account's lamports
+    pub payment_mint: Pubkey,
+    pub placed_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+use crate::MetalType;
+
+/// A product line collection created by the marketplace, e.g. "Gildore Gold 1oz"
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionConfig {
+    pub marketplace: Pubkey,
+    pub collection: Pubkey,
+    /// Metal every asset minted into the collection must be backed by
+    pub metal: MetalType,
+    /// Whether assets of the collection may be listed
+    pub tradable: bool,
+    pub bump: u8,
+}
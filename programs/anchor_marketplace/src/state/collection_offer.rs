@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+
+use crate::error::MarketplaceError;
+use crate::{MetalAttributes, MetalType};
+
+/// A buyer's standing offer for any assets of a collection backed by matching bars, filled one unit
+/// at a time. `price * quantity` is escrowed up front
+#[account]
+#[derive(InitSpace)]
+pub struct CollectionOffer {
+    pub marketplace: Pubkey,
+    pub buyer: Pubkey,
+    pub collection: Pubkey,
+    /// Chosen by the buyer so they can hold several offers at once
+    pub id: u64,
+    pub metal: MetalType,
+    /// Lowest accepted fineness in parts per 10,000
+    pub min_purity: u16,
+    pub min_weight_mg: u64,
+    /// Price per unit in `payment_mint` units
+    pub price: u64,
+    /// Units still wanted
+    pub quantity: u32,
+    /// Mint the offer is escrowed in, the wrapped SOL mint for native SOL which is escrowed in this account's lamports
+    pub payment_mint: Pubkey,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl CollectionOffer {
+    pub fn is_native_payment(&self) -> bool {
+        self.payment_mint == native_mint::ID
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Whether an asset backed by a bar with `attributes` can fill this offer
+    pub fn matches(&self, attributes: &MetalAttributes) -> bool {
+        attributes.metal == self.metal
+            && attributes.purity >= self.min_purity
+            && attributes.weight_mg >= self.min_weight_mg
+    }
+
+    /// Amount still held in escrow for the remaining units
+    pub fn escrowed_amount(&self) -> Result<u64> {
+        self.price
+            .checked_mul(self.quantity as u64)
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))
+    }
+}
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use crate::error::MarketplaceError;
+use crate::{MetalType, OracleConfig, USD_DECIMALS};
+
 #[account]
 #[derive(InitSpace)]
 pub struct Listing {
@@ -10,10 +13,230 @@ pub struct Listing {
     pub token_id: u16,
     pub is_active: bool,
     pub escrow_bump: u8,
+    pub kind: ListingKind,
+    /// After this the listing can no longer be bought and anyone can return the asset with `expire_listing`
+    pub expires_at: Option<i64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq)]
+pub enum ListingKind {
+    /// Sold at `Listing.price`, in USD with `USD_DECIMALS`
+    Fixed,
+    /// Sold at the live spot price of the metal content plus a seller premium
+    Spot {
+        metal: MetalType,
+        weight_mg: u64,
+        /// Fineness in parts per 10,000 (9999 is 999.9 fine)
+        purity: u16,
+        premium_bps: u16,
+    },
+    /// English auction settled to the highest bidder once `end_time` has passed, bids are
+    /// escrowed in `Bid` accounts and amounts are in `payment_mint` units
+    Auction {
+        /// Mint bids are placed in, the wrapped SOL mint for native SOL
+        payment_mint: Pubkey,
+        /// Lowest accepted first bid
+        reserve_price: u64,
+        /// Amount every later bid must beat the highest bid by
+        min_bid_increment: u64,
+        start_time: i64,
+        end_time: i64,
+        /// A bid placed this many seconds or less before `end_time` pushes it back to this long after the bid
+        extension_secs: u32,
+        highest_bid: u64,
+        highest_bidder: Option<Pubkey>,
+    },
+    /// Price declines linearly from `start_price` at `start_time` to `floor_price` at `end_time`,
+    /// then stays at the floor. Prices are in USD with `USD_DECIMALS`
+    Dutch {
+        start_price: u64,
+        floor_price: u64,
+        start_time: i64,
+        end_time: i64,
+    },
 }
+
+impl ListingKind {
+    pub fn validate(&self, now: i64) -> Result<()> {
+        match self {
+            ListingKind::Fixed => {}
+            ListingKind::Spot {
+                weight_mg,
+                purity,
+                premium_bps,
+                ..
+            } => {
+                require!(*weight_mg > 0, MarketplaceError::InvalidWeight);
+                require!(
+                    *purity > 0 && *purity < 10_000,
+                    MarketplaceError::InvalidPurity
+                );
+                require!(*premium_bps <= 10_000, MarketplaceError::InvalidPremiumBps);
+            }
+            ListingKind::Auction {
+                min_bid_increment,
+                start_time,
+                end_time,
+                highest_bid,
+                highest_bidder,
+                ..
+            } => {
+                require!(
+                    *min_bid_increment > 0
+                        && end_time > start_time
+                        && *end_time > now
+                        && *highest_bid == 0
+                        && highest_bidder.is_none(),
+                    MarketplaceError::InvalidAuction
+                );
+            }
+            ListingKind::Dutch {
+                start_price,
+                floor_price,
+                start_time,
+                end_time,
+            } => {
+                require!(
+                    start_price > floor_price && end_time > start_time,
+                    MarketplaceError::InvalidAuction
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Listing {
-    pub fn get_price_by_token_id(&self) -> u64 {
-        // TODO implement price fetching from oracle based on nft type
-        return self.price;
+    pub fn get_price_by_token_id(
+        &self,
+        oracle: &OracleConfig,
+        price_feed: Option<&AccountInfo>,
+    ) -> Result<u64> {
+        match self.kind {
+            ListingKind::Fixed => Ok(self.price),
+            ListingKind::Spot {
+                metal,
+                weight_mg,
+                purity,
+                premium_bps,
+            } => oracle.spot_price(metal, price_feed)?.value_of(
+                weight_mg,
+                purity,
+                premium_bps,
+                USD_DECIMALS,
+            ),
+            ListingKind::Auction { .. } => err!(MarketplaceError::AuctionListing),
+            ListingKind::Dutch {
+                start_price,
+                floor_price,
+                start_time,
+                end_time,
+            } => Self::declining_price(
+                start_price,
+                floor_price,
+                start_time,
+                end_time,
+                Clock::get()?.unix_timestamp,
+            ),
+        }
+    }
+
+    fn declining_price(
+        start_price: u64,
+        floor_price: u64,
+        start_time: i64,
+        end_time: i64,
+        now: i64,
+    ) -> Result<u64> {
+        require!(now >= start_time, MarketplaceError::ListingNotStarted);
+        if now >= end_time {
+            return Ok(floor_price);
+        }
+
+        let elapsed = (now - start_time) as u128;
+        let duration = (end_time - start_time) as u128;
+        let decline = ((start_price - floor_price) as u128)
+            .checked_mul(elapsed)
+            .ok_or(MarketplaceError::MathOverflowError)?
+            / duration;
+
+        Ok(start_price - decline as u64)
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    pub fn is_auction(&self) -> bool {
+        matches!(self.kind, ListingKind::Auction { .. })
+    }
+
+    /// Current highest bidder of an auction listing
+    pub fn highest_bidder(&self) -> Option<Pubkey> {
+        match self.kind {
+            ListingKind::Auction { highest_bidder, .. } => highest_bidder,
+            _ => None,
+        }
+    }
+
+    /// Records `amount` from `bidder` as the highest bid, extending the auction when the bid
+    /// lands within its extension window
+    pub fn place_bid(
+        &mut self,
+        bidder: Pubkey,
+        amount: u64,
+        payment_mint: &Pubkey,
+        now: i64,
+    ) -> Result<()> {
+        let ListingKind::Auction {
+            payment_mint: auction_mint,
+            reserve_price,
+            min_bid_increment,
+            start_time,
+            end_time,
+            extension_secs,
+            highest_bid,
+            highest_bidder,
+            ..
+        } = &mut self.kind
+        else {
+            return err!(MarketplaceError::NotAnAuction);
+        };
+
+        require_keys_eq!(
+            *auction_mint,
+            *payment_mint,
+            MarketplaceError::PaymentMintMismatch
+        );
+        require!(
+            now >= *start_time && now < *end_time,
+            MarketplaceError::AuctionNotActive
+        );
+
+        let minimum_bid = match highest_bidder {
+            Some(_) => highest_bid
+                .checked_add(*min_bid_increment)
+                .ok_or(MarketplaceError::MathOverflowError)?,
+            None => *reserve_price,
+        };
+        require!(amount >= minimum_bid, MarketplaceError::BidTooLow);
+
+        *highest_bid = amount;
+        *highest_bidder = Some(bidder);
+
+        let extended_end = now
+            .checked_add(*extension_secs as i64)
+            .ok_or(MarketplaceError::MathOverflowError)?;
+        *end_time = (*end_time).max(extended_end);
+
+        Ok(())
+    }
+
+    /// End time of an auction listing
+    pub fn auction_end_time(&self) -> Result<i64> {
+        match self.kind {
+            ListingKind::Auction { end_time, .. } => Ok(end_time),
+            _ => err!(MarketplaceError::NotAnAuction),
+        }
     }
 }
@@ -1,12 +1,174 @@
 use anchor_lang::prelude::*;
 
+use crate::error::MarketplaceError;
+use crate::{MetalType, PriceFeed, SellerProfile, SpotPrice};
+
 #[account]
 #[derive(InitSpace)]
 pub struct Marketplace {
+    /// Seeds the marketplace PDA so it survives admin rotation
+    pub id: u64,
     pub admin: Pubkey,
     pub treasury_bump: u8,
     pub bump: u8,
     pub fee_bps: u16,
     #[max_len(32)]
     pub name: String,
+    pub oracle: OracleConfig,
+    /// Seconds a fee increase waits before it applies
+    pub fee_timelock: u64,
+    pub pending_fee: Option<PendingFee>,
+    /// Shorter fee timelock waiting for the current timelock to pass
+    pub pending_fee_timelock: Option<PendingFeeTimelock>,
+    /// Admin proposed by `propose_admin`, awaiting `accept_admin`
+    pub pending_admin: Option<Pubkey>,
+    pub seller_permission: SellerPermission,
+    /// Lamports paid out of an expired listing's rent to whoever cranks `expire_listing`
+    pub expiry_bounty: u64,
+}
+
+impl Marketplace {
+    /// Fee in basis points charged at `now`, including a pending increase once its timelock passed
+    pub fn fee_bps_at(&self, now: i64) -> u16 {
+        match self.pending_fee {
+            Some(pending_fee) if now >= pending_fee.effective_at => pending_fee.fee_bps,
+            _ => self.fee_bps,
+        }
+    }
+
+    /// Fee timelock in force at `now`, including a pending decrease once the previous timelock passed
+    pub fn fee_timelock_at(&self, now: i64) -> u64 {
+        match self.pending_fee_timelock {
+            Some(pending) if now >= pending.effective_at => pending.fee_timelock,
+            _ => self.fee_timelock,
+        }
+    }
+
+    /// Apply `fee_bps` immediately when it does not raise the fee, otherwise schedule it behind the timelock
+    pub fn set_fee_bps(&mut self, fee_bps: u16, now: i64) -> Result<()> {
+        require!(fee_bps <= 10000, MarketplaceError::InvalidFeeBps);
+
+        self.fee_bps = self.fee_bps_at(now);
+        self.pending_fee = None;
+
+        let fee_timelock = self.fee_timelock_at(now);
+        if fee_bps <= self.fee_bps || fee_timelock == 0 {
+            self.fee_bps = fee_bps;
+        } else {
+            self.pending_fee = Some(PendingFee {
+                fee_bps,
+                effective_at: Self::after_timelock(now, fee_timelock)?,
+            });
+        }
+        Ok(())
+    }
+
+    /// Apply `fee_timelock` immediately when it does not shorten the timelock, otherwise schedule it
+    /// behind the current timelock so a shorter delay cannot be used to rush a fee increase
+    pub fn set_fee_timelock(&mut self, fee_timelock: u64, now: i64) -> Result<()> {
+        self.fee_timelock = self.fee_timelock_at(now);
+        self.pending_fee_timelock = None;
+
+        if fee_timelock >= self.fee_timelock {
+            self.fee_timelock = fee_timelock;
+        } else {
+            self.pending_fee_timelock = Some(PendingFeeTimelock {
+                fee_timelock,
+                effective_at: Self::after_timelock(now, self.fee_timelock)?,
+            });
+        }
+        Ok(())
+    }
+
+    fn after_timelock(now: i64, timelock: u64) -> Result<i64> {
+        i64::try_from(timelock)
+            .ok()
+            .and_then(|timelock| now.checked_add(timelock))
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))
+    }
+
+    /// Whether `seller` may create listings under the current seller permission
+    pub fn can_list(&self, seller: &Pubkey, profile: &SellerProfile) -> bool {
+        if *seller == self.admin {
+            return true;
+        }
+        match self.seller_permission {
+            SellerPermission::Open => true,
+            SellerPermission::Allowlisted => profile.approved,
+            SellerPermission::AdminOnly => false,
+        }
+    }
+
+    /// Marketplace fee owed on `amount`
+    pub fn calculate_fee(&self, amount: u64) -> Result<u64> {
+        let fee_bps = self.fee_bps_at(Clock::get()?.unix_timestamp);
+        amount
+            .checked_mul(fee_bps as u64)
+            .and_then(|mul_result| mul_result.checked_div(10_000))
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))
+    }
+}
+
+/// Who may list assets on the marketplace
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq)]
+pub enum SellerPermission {
+    /// Any holder of a marketplace asset
+    Open,
+    /// Sellers the admin approved through their seller profile
+    Allowlisted,
+    AdminOnly,
+}
+
+/// Fee increase waiting for the marketplace fee timelock to pass
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq)]
+pub struct PendingFee {
+    pub fee_bps: u16,
+    pub effective_at: i64,
+}
+
+/// Fee timelock decrease waiting for the current fee timelock to pass
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq)]
+pub struct PendingFeeTimelock {
+    pub fee_timelock: u64,
+    pub effective_at: i64,
+}
+
+/// Spot price feeds accepted by the marketplace and the bounds a price must satisfy
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq)]
+pub struct OracleConfig {
+    pub gold_price_feed: Pubkey,
+    pub silver_price_feed: Pubkey,
+    /// Maximum age of a price in seconds
+    pub max_price_age: u64,
+    /// Maximum confidence interval as basis points of the price
+    pub max_confidence_bps: u16,
+}
+
+impl OracleConfig {
+    pub fn price_feed(&self, metal: MetalType) -> Pubkey {
+        match metal {
+            MetalType::Gold => self.gold_price_feed,
+            MetalType::Silver => self.silver_price_feed,
+        }
+    }
+
+    /// Loads a fresh spot price for `metal` from `price_feed`, which must be the configured feed
+    pub fn spot_price(
+        &self,
+        metal: MetalType,
+        price_feed: Option<&AccountInfo>,
+    ) -> Result<SpotPrice> {
+        let price_feed = price_feed.ok_or(MarketplaceError::PriceFeedMissing)?;
+        require_keys_eq!(
+            price_feed.key(),
+            self.price_feed(metal),
+            MarketplaceError::PriceFeedMismatch
+        );
+
+        PriceFeed::load(price_feed)?.get_price_no_older_than(
+            Clock::get()?.unix_timestamp,
+            self.max_price_age,
+            self.max_confidence_bps,
+        )
+    }
 }
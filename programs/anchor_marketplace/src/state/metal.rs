@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::BaseAssetV1,
+    fetch_plugin,
+    types::{Attribute, Attributes, PluginType},
+};
+
+use crate::error::MarketplaceError;
+use crate::{OracleConfig, TROY_OUNCE_IN_TEN_THOUSANDTH_MG, USD_DECIMALS};
+
+pub const METAL_KEY: &str = "metal";
+pub const PURITY_KEY: &str = "purity";
+pub const WEIGHT_MG_KEY: &str = "weight_mg";
+pub const WEIGHT_TROY_OZ_KEY: &str = "weight_troy_oz";
+pub const VAULT_ID_KEY: &str = "vault_id";
+pub const SERIAL_NUMBER_KEY: &str = "serial_number";
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MetalType {
+    Gold,
+    Silver,
+}
+
+impl MetalType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetalType::Gold => "gold",
+            MetalType::Silver => "silver",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "gold" => Some(MetalType::Gold),
+            "silver" => Some(MetalType::Silver),
+            _ => None,
+        }
+    }
+}
+
+/// Physical properties of the bar an asset is backed by, stored on the asset as an `Attributes` plugin
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct MetalAttributes {
+    pub metal: MetalType,
+    /// Fineness in parts per 10,000 (9999 is 999.9 fine)
+    pub purity: u16,
+    pub weight_mg: u64,
+    pub vault_id: String,
+    pub serial_number: String,
+}
+
+impl MetalAttributes {
+    /// Value of the metal content in USD at the current spot price
+    pub fn spot_value(
+        &self,
+        oracle: &OracleConfig,
+        price_feed: Option<&AccountInfo>,
+    ) -> Result<u64> {
+        oracle.spot_price(self.metal, price_feed)?.value_of(
+            self.weight_mg,
+            self.purity,
+            0,
+            USD_DECIMALS,
+        )
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.purity > 0 && self.purity < 10_000,
+            MarketplaceError::InvalidPurity
+        );
+        require!(self.weight_mg > 0, MarketplaceError::InvalidWeight);
+        require!(
+            !self.vault_id.is_empty() && self.vault_id.len() <= 32,
+            MarketplaceError::InvalidVaultId
+        );
+        require!(
+            !self.serial_number.is_empty() && self.serial_number.len() <= 32,
+            MarketplaceError::InvalidSerialNumber
+        );
+        Ok(())
+    }
+
+    /// Reads the metal attributes written to an MPL Core asset by `create_nft`
+    pub fn from_asset(asset: &AccountInfo) -> Result<Self> {
+        let (_, attributes, _) =
+            fetch_plugin::<BaseAssetV1, Attributes>(asset, PluginType::Attributes)
+                .map_err(|_| error!(MarketplaceError::InvalidMetalAttributes))?;
+        Self::from_attributes(&attributes)
+    }
+
+    pub fn from_attributes(attributes: &Attributes) -> Result<Self> {
+        let value_of = |key: &str| {
+            attributes
+                .attribute_list
+                .iter()
+                .find(|attribute| attribute.key == key)
+                .map(|attribute| attribute.value.as_str())
+                .ok_or_else(|| error!(MarketplaceError::InvalidMetalAttributes))
+        };
+
+        let metal = MetalType::parse(value_of(METAL_KEY)?)
+            .ok_or_else(|| error!(MarketplaceError::InvalidMetalAttributes))?;
+        let purity = value_of(PURITY_KEY)?
+            .split_once('.')
+            .and_then(|(whole, tenths)| {
+                let whole = whole.parse::<u16>().ok()?;
+                let tenths = tenths.parse::<u16>().ok().filter(|tenths| *tenths < 10)?;
+                whole.checked_mul(10)?.checked_add(tenths)
+            })
+            .ok_or_else(|| error!(MarketplaceError::InvalidMetalAttributes))?;
+        let weight_mg = value_of(WEIGHT_MG_KEY)?
+            .parse::<u64>()
+            .map_err(|_| error!(MarketplaceError::InvalidMetalAttributes))?;
+
+        Ok(Self {
+            metal,
+            purity,
+            weight_mg,
+            vault_id: value_of(VAULT_ID_KEY)?.to_string(),
+            serial_number: value_of(SERIAL_NUMBER_KEY)?.to_string(),
+        })
+    }
+
+    pub fn to_attributes(&self) -> Attributes {
+        let troy_oz_ten_thousandths = (self.weight_mg as u128) * 100_000_000
+            / TROY_OUNCE_IN_TEN_THOUSANDTH_MG;
+
+        Attributes {
+            attribute_list: vec![
+                attribute(METAL_KEY, self.metal.as_str().to_string()),
+                attribute(
+                    PURITY_KEY,
+                    format!("{}.{}", self.purity / 10, self.purity % 10),
+                ),
+                attribute(WEIGHT_MG_KEY, self.weight_mg.to_string()),
+                attribute(
+                    WEIGHT_TROY_OZ_KEY,
+                    format!(
+                        "{}.{:04}",
+                        troy_oz_ten_thousandths / 10_000,
+                        troy_oz_ten_thousandths % 10_000
+                    ),
+                ),
+                attribute(VAULT_ID_KEY, self.vault_id.clone()),
+                attribute(SERIAL_NUMBER_KEY, self.serial_number.clone()),
+            ],
+        }
+    }
+}
+
+fn attribute(key: &str, value: String) -> Attribute {
+    Attribute {
+        key: key.to_string(),
+        value,
+    }
+}
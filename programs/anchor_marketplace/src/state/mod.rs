@@ -3,3 +3,42 @@ pub use marketplace::*;
 
 pub mod listing;
 pub use listing::*;
+
+pub mod metal;
+pub use metal::*;
+
+pub mod price_feed;
+pub use price_feed::*;
+
+pub mod payment_mint;
+pub use payment_mint::*;
+
+pub mod vault;
+pub use vault::*;
+
+pub mod vault_partner;
+pub use vault_partner::*;
+
+pub mod redemption;
+pub use redemption::*;
+
+pub mod seller_profile;
+pub use seller_profile::*;
+
+pub mod sale_record;
+pub use sale_record::*;
+
+pub mod royalties;
+pub use royalties::*;
+
+pub mod collection_config;
+pub use collection_config::*;
+
+pub mod bid;
+pub use bid::*;
+
+pub mod offer;
+pub use offer::*;
+
+pub mod collection_offer;
+pub use collection_offer::*;
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+
+/// A buyer's offer on an asset, listed or not, escrowed until accepted, cancelled or expired
+#[account]
+#[derive(InitSpace)]
+pub struct Offer {
+    pub marketplace: Pubkey,
+    pub asset: Pubkey,
+    pub buyer: Pubkey,
+    /// Amount escrowed in `payment_mint` units
+    pub amount: u64,
+    /// Mint the offer is escrowed in, the wrapped SOL mint for native SOL which is escrowed in this account's lamports
+    pub payment_mint: Pubkey,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl Offer {
+    pub fn is_native_payment(&self) -> bool {
+        self.payment_mint == native_mint::ID
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
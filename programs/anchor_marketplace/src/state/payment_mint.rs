@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+
+use crate::error::MarketplaceError;
+use crate::{OracleConfig, PriceFeed, SpotPrice, USD_DECIMALS};
+
+/// A mint the marketplace accepts payment in, native SOL is represented by the wrapped SOL mint
+#[account]
+#[derive(InitSpace)]
+pub struct PaymentMint {
+    pub marketplace: Pubkey,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub enabled: bool,
+    /// Feed quoting this mint in USD, `None` for USD stablecoins which are converted 1:1
+    pub price_feed: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl PaymentMint {
+    pub fn is_native(&self) -> bool {
+        self.mint == native_mint::ID
+    }
+
+    /// Converts an amount in USD (`USD_DECIMALS`) into base units of this mint
+    pub fn amount_from_usd(
+        &self,
+        usd_amount: u64,
+        oracle: &OracleConfig,
+        price_feed: Option<&AccountInfo>,
+    ) -> Result<u64> {
+        let (mint_scale, usd_scale) = self.scales()?;
+
+        let amount = match self.usd_price(oracle, price_feed)? {
+            None => (usd_amount as u128)
+                .checked_mul(mint_scale)
+                .and_then(|mul_result| mul_result.checked_div(usd_scale)),
+            Some(spot_price) => {
+                let expo_scale = 10u128
+                    .checked_pow(spot_price.expo.unsigned_abs())
+                    .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+
+                let scaled_usd = (usd_amount as u128).checked_mul(mint_scale);
+                let (numerator, denominator) = if spot_price.expo < 0 {
+                    (
+                        scaled_usd.and_then(|mul_result| mul_result.checked_mul(expo_scale)),
+                        (spot_price.price as u128).checked_mul(usd_scale),
+                    )
+                } else {
+                    (
+                        scaled_usd,
+                        (spot_price.price as u128)
+                            .checked_mul(usd_scale)
+                            .and_then(|mul_result| mul_result.checked_mul(expo_scale)),
+                    )
+                };
+
+                numerator
+                    .zip(denominator)
+                    .and_then(|(numerator, denominator)| numerator.checked_div(denominator))
+            }
+        }
+        .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+
+        u64::try_from(amount).map_err(|_| error!(MarketplaceError::MathOverflowError))
+    }
+
+    /// Converts base units of this mint into USD (`USD_DECIMALS`), the inverse of `amount_from_usd`
+    pub fn usd_from_amount(
+        &self,
+        amount: u64,
+        oracle: &OracleConfig,
+        price_feed: Option<&AccountInfo>,
+    ) -> Result<u64> {
+        let (mint_scale, usd_scale) = self.scales()?;
+
+        let scaled_amount = (amount as u128).checked_mul(usd_scale);
+        let usd_amount = match self.usd_price(oracle, price_feed)? {
+            None => scaled_amount.and_then(|mul_result| mul_result.checked_div(mint_scale)),
+            Some(spot_price) => {
+                let expo_scale = 10u128
+                    .checked_pow(spot_price.expo.unsigned_abs())
+                    .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+
+                let value = scaled_amount
+                    .and_then(|mul_result| mul_result.checked_mul(spot_price.price as u128));
+                if spot_price.expo < 0 {
+                    value.and_then(|value| value.checked_div(mint_scale.checked_mul(expo_scale)?))
+                } else {
+                    value
+                        .and_then(|value| value.checked_mul(expo_scale))
+                        .and_then(|value| value.checked_div(mint_scale))
+                }
+            }
+        }
+        .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+
+        u64::try_from(usd_amount).map_err(|_| error!(MarketplaceError::MathOverflowError))
+    }
+
+    fn scales(&self) -> Result<(u128, u128)> {
+        let mint_scale = 10u128
+            .checked_pow(self.decimals as u32)
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+        let usd_scale = 10u128
+            .checked_pow(USD_DECIMALS as u32)
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+        Ok((mint_scale, usd_scale))
+    }
+
+    /// Fresh USD price of this mint, `None` for stablecoins converted 1:1
+    fn usd_price(
+        &self,
+        oracle: &OracleConfig,
+        price_feed: Option<&AccountInfo>,
+    ) -> Result<Option<SpotPrice>> {
+        let Some(expected_feed) = self.price_feed else {
+            return Ok(None);
+        };
+
+        let price_feed = price_feed.ok_or(MarketplaceError::PriceFeedMissing)?;
+        require_keys_eq!(
+            price_feed.key(),
+            expected_feed,
+            MarketplaceError::PriceFeedMismatch
+        );
+
+        PriceFeed::load(price_feed)?
+            .get_price_no_older_than(
+                Clock::get()?.unix_timestamp,
+                oracle.max_price_age,
+                oracle.max_confidence_bps,
+            )
+            .map(Some)
+    }
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+
+// Offsets into a Pyth-style (legacy v2) price account
+const MAGIC: u32 = 0xa1b2_c3d4;
+const VERSION: u32 = 2;
+const ACCOUNT_TYPE_PRICE: u32 = 3;
+const STATUS_TRADING: u32 = 1;
+
+const MAGIC_OFFSET: usize = 0;
+const VERSION_OFFSET: usize = 4;
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const EXPO_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_CONF_OFFSET: usize = 216;
+const AGG_STATUS_OFFSET: usize = 224;
+const MIN_PRICE_ACCOUNT_LEN: usize = 240;
+
+/// One troy ounce expressed in ten-thousandths of a milligram (31.1034768 g)
+pub const TROY_OUNCE_IN_TEN_THOUSANDTH_MG: u128 = 311_034_768;
+
+/// Aggregate price read from a spot price feed account, quoted per troy ounce
+pub struct PriceFeed {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub status: u32,
+}
+
+impl PriceFeed {
+    pub fn load(feed: &AccountInfo) -> Result<Self> {
+        let data = feed.try_borrow_data()?;
+        require!(
+            data.len() >= MIN_PRICE_ACCOUNT_LEN,
+            MarketplaceError::InvalidPriceFeed
+        );
+        require!(
+            read_u32(&data, MAGIC_OFFSET) == MAGIC
+                && read_u32(&data, VERSION_OFFSET) == VERSION
+                && read_u32(&data, ACCOUNT_TYPE_OFFSET) == ACCOUNT_TYPE_PRICE,
+            MarketplaceError::InvalidPriceFeed
+        );
+
+        Ok(Self {
+            price: read_u64(&data, AGG_PRICE_OFFSET) as i64,
+            conf: read_u64(&data, AGG_CONF_OFFSET),
+            expo: read_u32(&data, EXPO_OFFSET) as i32,
+            publish_time: read_u64(&data, TIMESTAMP_OFFSET) as i64,
+            status: read_u32(&data, AGG_STATUS_OFFSET),
+        })
+    }
+
+    /// Rejects prices that are not trading, older than `max_age` seconds or whose confidence interval is wider than `max_confidence_bps` of the price
+    pub fn get_price_no_older_than(
+        &self,
+        now: i64,
+        max_age: u64,
+        max_confidence_bps: u16,
+    ) -> Result<SpotPrice> {
+        require!(
+            self.status == STATUS_TRADING && self.price > 0,
+            MarketplaceError::InvalidPrice
+        );
+
+        let age = now.saturating_sub(self.publish_time);
+        require!(
+            age >= 0 && (age as u64) <= max_age,
+            MarketplaceError::StalePrice
+        );
+
+        let price = self.price as u64;
+        let max_conf = (price as u128)
+            .checked_mul(max_confidence_bps as u128)
+            .and_then(|mul_result| mul_result.checked_div(10_000))
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+        require!(
+            (self.conf as u128) <= max_conf,
+            MarketplaceError::PriceConfidenceTooWide
+        );
+
+        Ok(SpotPrice {
+            price,
+            expo: self.expo,
+        })
+    }
+}
+
+/// Validated spot price per troy ounce, scaled by `10^expo`
+pub struct SpotPrice {
+    pub price: u64,
+    pub expo: i32,
+}
+
+impl SpotPrice {
+    /// Value of `weight_mg` milligrams of metal at `purity` (parts per 10,000) plus `premium_bps`, in units of `quote_decimals`
+    pub fn value_of(
+        &self,
+        weight_mg: u64,
+        purity: u16,
+        premium_bps: u16,
+        quote_decimals: u8,
+    ) -> Result<u64> {
+        let quote_scale = 10u128
+            .checked_pow(quote_decimals as u32)
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+        let expo_scale = 10u128
+            .checked_pow(self.expo.unsigned_abs())
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+
+        // Price per troy ounce in quote units
+        let price_per_ounce = if self.expo < 0 {
+            (self.price as u128)
+                .checked_mul(quote_scale)
+                .and_then(|mul_result| mul_result.checked_div(expo_scale))
+        } else {
+            (self.price as u128)
+                .checked_mul(quote_scale)
+                .and_then(|mul_result| mul_result.checked_mul(expo_scale))
+        };
+
+        let value = price_per_ounce
+            .and_then(|ounce_price| ounce_price.checked_mul(weight_mg as u128))
+            .and_then(|mul_result| mul_result.checked_mul(10_000))
+            .and_then(|mul_result| mul_result.checked_div(TROY_OUNCE_IN_TEN_THOUSANDTH_MG))
+            .and_then(|metal_value| metal_value.checked_mul(purity as u128))
+            .and_then(|mul_result| mul_result.checked_div(10_000))
+            .and_then(|fine_value| fine_value.checked_mul(10_000 + premium_bps as u128))
+            .and_then(|mul_result| mul_result.checked_div(10_000))
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+
+        u64::try_from(value).map_err(|_| error!(MarketplaceError::MathOverflowError))
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_le_bytes(bytes)
+}
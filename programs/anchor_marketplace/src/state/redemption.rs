@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+
+/// Room for a sealed box (48 bytes of overhead) around a full postal address
+pub const MAX_ENCRYPTED_SHIPPING_DETAILS_LEN: usize = 512;
+
+/// Seconds after which a holder can cancel a redemption the vault partner accepted but never shipped
+pub const REDEMPTION_SHIPPING_DEADLINE: i64 = 30 * 24 * 60 * 60;
+
+/// A holder's request to redeem an asset for the physical bar backing it
+#[account]
+#[derive(InitSpace)]
+pub struct Redemption {
+    pub marketplace: Pubkey,
+    pub owner: Pubkey,
+    pub asset: Pubkey,
+    pub vault: Pubkey,
+    /// Mint the fee was escrowed in, the wrapped SOL mint for native SOL which is escrowed in this account's lamports
+    pub payment_mint: Pubkey,
+    pub fee: u64,
+    pub status: RedemptionStatus,
+    pub requested_at: i64,
+    pub bump: u8,
+    /// Vault partner the shipping details were sealed to
+    pub vault_partner: Pubkey,
+    /// x25519 key of `vault_partner` at request time
+    pub encryption_key: [u8; 32],
+    /// Hash of the plaintext shipping details and a salt, proving what was submitted
+    pub shipping_commitment: [u8; 32],
+    /// Shipping details sealed to `encryption_key`, readable only by the vault partner
+    #[max_len(MAX_ENCRYPTED_SHIPPING_DETAILS_LEN)]
+    pub encrypted_shipping_details: Vec<u8>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq)]
+pub enum RedemptionStatus {
+    /// Waiting for the vault partner to review
+    Requested,
+    /// Accepted by the vault partner, waiting for the bar to ship
+    Accepted,
+}
+
+impl Redemption {
+    pub fn is_native_payment(&self) -> bool {
+        self.payment_mint == native_mint::ID
+    }
+
+    /// Holders can withdraw a request the vault partner has not reviewed yet, or any request
+    /// still unshipped once the shipping deadline has passed
+    pub fn can_cancel(&self, now: i64) -> bool {
+        self.status == RedemptionStatus::Requested
+            || now
+                >= self
+                    .requested_at
+                    .saturating_add(REDEMPTION_SHIPPING_DEADLINE)
+    }
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::{BaseAssetV1, BaseCollectionV1},
+    fetch_plugin,
+    types::{Creator, Plugin, PluginType, Royalties, RuleSet},
+};
+
+use crate::error::MarketplaceError;
+
+pub const MAX_ROYALTY_CREATORS: usize = 5;
+
+/// Creator royalties written to an asset's MPL Core Royalties plugin at mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct RoyaltyConfig {
+    pub basis_points: u16,
+    pub creators: Vec<CreatorShare>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct CreatorShare {
+    pub address: Pubkey,
+    /// Share of the royalty in percent, shares add up to 100
+    pub percentage: u8,
+}
+
+impl RoyaltyConfig {
+    /// `fee_bps` is the marketplace fee the royalty is charged alongside on every sale
+    pub fn validate(&self, fee_bps: u16) -> Result<()> {
+        require!(
+            self.basis_points <= 10_000,
+            MarketplaceError::InvalidRoyalties
+        );
+        require!(
+            self.basis_points as u32 + fee_bps as u32 <= 10_000,
+            MarketplaceError::FeesExceedPrice
+        );
+        require!(
+            !self.creators.is_empty() && self.creators.len() <= MAX_ROYALTY_CREATORS,
+            MarketplaceError::InvalidRoyalties
+        );
+        require!(
+            self.creators
+                .iter()
+                .map(|creator| creator.percentage as u16)
+                .sum::<u16>()
+                == 100,
+            MarketplaceError::InvalidRoyalties
+        );
+        Ok(())
+    }
+
+    pub fn to_plugin(&self) -> Plugin {
+        Plugin::Royalties(Royalties {
+            basis_points: self.basis_points,
+            creators: self
+                .creators
+                .iter()
+                .map(|creator| Creator {
+                    address: creator.address,
+                    percentage: creator.percentage,
+                })
+                .collect(),
+            // Royalties are paid out by the marketplace itself on purchase
+            rule_set: RuleSet::None,
+        })
+    }
+}
+
+/// Royalties owed on a sale of `asset`, its own plugin taking precedence over its collection's
+pub fn fetch_royalties(asset: &AccountInfo, collection: Option<&AccountInfo>) -> Option<Royalties> {
+    if let Ok((_, royalties, _)) =
+        fetch_plugin::<BaseAssetV1, Royalties>(asset, PluginType::Royalties)
+    {
+        return Some(royalties);
+    }
+
+    collection
+        .and_then(|collection| {
+            fetch_plugin::<BaseCollectionV1, Royalties>(collection, PluginType::Royalties).ok()
+        })
+        .map(|(_, royalties, _)| royalties)
+}
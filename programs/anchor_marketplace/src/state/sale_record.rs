@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::AssetSold;
+
+/// Most recent sale of an asset on a marketplace, earlier sales are kept in `AssetSold` events
+#[account]
+#[derive(InitSpace)]
+pub struct SaleRecord {
+    pub marketplace: Pubkey,
+    pub asset: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    /// Sale price in USD with `USD_DECIMALS`
+    pub price: u64,
+    /// Mint the buyer paid with, the wrapped SOL mint for native SOL
+    pub payment_mint: Pubkey,
+    /// Amount paid in `payment_mint` units, marketplace fee included
+    pub amount: u64,
+    pub fee: u64,
+    pub slot: u64,
+    pub sold_at: i64,
+    /// Number of times the asset sold on this marketplace
+    pub sale_count: u64,
+    pub bump: u8,
+}
+
+impl SaleRecord {
+    /// Overwrites the record with `sale` and emits it
+    pub fn record(&mut self, sale: AssetSold, bump: u8) -> Result<()> {
+        let sale_count = self
+            .sale_count
+            .checked_add(1)
+            .ok_or(MarketplaceError::MathOverflowError)?;
+
+        *self = SaleRecord {
+            marketplace: sale.marketplace,
+            asset: sale.asset,
+            seller: sale.seller,
+            buyer: sale.buyer,
+            price: sale.price,
+            payment_mint: sale.payment_mint,
+            amount: sale.amount,
+            fee: sale.fee,
+            slot: sale.slot,
+            sold_at: sale.timestamp,
+            sale_count,
+            bump,
+        };
+
+        emit!(sale);
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+
+/// Per seller approval and trading history on a marketplace
+#[account]
+#[derive(InitSpace)]
+pub struct SellerProfile {
+    pub marketplace: Pubkey,
+    pub seller: Pubkey,
+    /// Set by the admin, required to list while the marketplace is allowlisted
+    pub approved: bool,
+    pub listing_count: u64,
+    pub sale_count: u64,
+    /// Sum of sale prices in USD
+    pub volume: u64,
+    pub bump: u8,
+}
+
+impl SellerProfile {
+    pub fn record_listing(&mut self) -> Result<()> {
+        self.listing_count = self
+            .listing_count
+            .checked_add(1)
+            .ok_or(MarketplaceError::MathOverflowError)?;
+        Ok(())
+    }
+
+    pub fn record_sale(&mut self, usd_price: u64) -> Result<()> {
+        self.sale_count = self
+            .sale_count
+            .checked_add(1)
+            .ok_or(MarketplaceError::MathOverflowError)?;
+        self.volume = self
+            .volume
+            .checked_add(usd_price)
+            .ok_or(MarketplaceError::MathOverflowError)?;
+        Ok(())
+    }
+}
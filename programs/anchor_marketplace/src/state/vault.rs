@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::MetalType;
+
+/// Physical metal held by a vault partner that minted assets are backed by
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub marketplace: Pubkey,
+    pub metal: MetalType,
+    /// Metal held in custody in milligrams
+    pub total_mg: u64,
+    /// Metal backing minted assets in milligrams
+    pub minted_mg: u64,
+    /// Metal set aside and unavailable for minting in milligrams
+    pub reserved_mg: u64,
+    pub bump: u8,
+    #[max_len(32)]
+    pub vault_id: String,
+}
+
+impl Vault {
+    pub fn available_mg(&self) -> Result<u64> {
+        self.total_mg
+            .checked_sub(self.minted_mg)
+            .and_then(|unminted| unminted.checked_sub(self.reserved_mg))
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))
+    }
+
+    /// Backs a newly minted asset of `weight_mg` with metal from this vault
+    pub fn debit_minted(&mut self, weight_mg: u64) -> Result<()> {
+        require!(
+            weight_mg <= self.available_mg()?,
+            MarketplaceError::InsufficientVaultInventory
+        );
+        self.minted_mg = self
+            .minted_mg
+            .checked_add(weight_mg)
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+        Ok(())
+    }
+
+    /// Releases the bar backing a redeemed asset out of custody
+    pub fn credit_redeemed(&mut self, weight_mg: u64) -> Result<()> {
+        self.minted_mg = self
+            .minted_mg
+            .checked_sub(weight_mg)
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+        self.total_mg = self
+            .total_mg
+            .checked_sub(weight_mg)
+            .ok_or_else(|| error!(MarketplaceError::MathOverflowError))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketplaceError;
+use crate::Vault;
+
+pub const MAX_PARTNER_VAULTS: usize = 10;
+
+/// Licensed custodian of the physical metal held in one or more vaults
+#[account]
+#[derive(InitSpace)]
+pub struct VaultPartner {
+    pub marketplace: Pubkey,
+    pub authority: Pubkey,
+    pub status: VaultPartnerStatus,
+    pub bump: u8,
+    /// x25519 public key holders seal redemption shipping details to
+    pub encryption_key: [u8; 32],
+    #[max_len(32)]
+    pub name: String,
+    /// ISO 3166 country code the partner operates under
+    #[max_len(3)]
+    pub jurisdiction_code: String,
+    #[max_len(MAX_PARTNER_VAULTS)]
+    pub vaults: Vec<Pubkey>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq)]
+pub enum VaultPartnerStatus {
+    Active,
+    Suspended,
+}
+
+impl VaultPartner {
+    pub fn is_active(&self) -> bool {
+        self.status == VaultPartnerStatus::Active
+    }
+
+    pub fn custodies(&self, vault: &Pubkey) -> bool {
+        self.vaults.contains(vault)
+    }
+
+    /// Replaces the vaults this partner custodies, each of which must be a vault of its marketplace
+    pub fn set_vaults(&mut self, vaults: &[AccountInfo]) -> Result<()> {
+        require!(
+            vaults.len() <= MAX_PARTNER_VAULTS,
+            MarketplaceError::TooManyVaults
+        );
+
+        let mut vault_keys = Vec::with_capacity(vaults.len());
+        for vault in vaults {
+            require_keys_eq!(*vault.owner, crate::ID, MarketplaceError::VaultMismatch);
+            let vault_account = Vault::try_deserialize(&mut &vault.try_borrow_data()?[..])?;
+            require_keys_eq!(
+                vault_account.marketplace,
+                self.marketplace,
+                MarketplaceError::VaultMismatch
+            );
+            require!(
+                !vault_keys.contains(vault.key),
+                MarketplaceError::DuplicateVault
+            );
+            vault_keys.push(vault.key());
+        }
+
+        self.vaults = vault_keys;
+        Ok(())
+    }
+}
@@ -13,6 +13,8 @@ import {
 } from "@metaplex-foundation/umi";
 import { irysUploader } from "@metaplex-foundation/umi-uploader-irys";
 import { readFile } from "fs/promises";
+import { createHash, generateKeyPairSync, randomBytes } from "crypto";
+import { expect } from "chai";
 
 import path from "path";
 
@@ -42,10 +44,32 @@ describe("anchor_marketplace", () => {
   umi.use(irysUploader());
   umi.use(signerIdentity(signer));
 
+  // mock spot price feeds loaded from tests/fixtures
+  const gold_price_feed = new anchor.web3.PublicKey(
+    "DpzWFuSmRRVukgpDRCWifN9JwBsA5MY3esGqb8fdg4Mf"
+  );
+  const silver_price_feed = new anchor.web3.PublicKey(
+    "5dJFpzVJ7wSRwh9R5JeVmAMtrF5jL9Avzqo4uv5rkkXQ"
+  );
+
+  // devnet USDC
+  const payment_mint = new anchor.web3.PublicKey(
+    "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"
+  );
+
+  // x25519 key the vault partner receives sealed shipping details on
+  const vault_partner_encryption_key = generateKeyPairSync("x25519")
+    .publicKey.export({ format: "der", type: "spki" })
+    .subarray(-32);
+
   let asset: anchor.web3.Keypair;
+  let spot_asset: anchor.web3.Keypair;
+  let gold_collection: anchor.web3.Keypair;
+  let metadata_uri: string;
 
+  const marketplace_id = new anchor.BN(0);
   let marketplace = anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("marketplace"), admin_wallet.publicKey.toBuffer()],
+    [Buffer.from("marketplace"), marketplace_id.toArrayLike(Buffer, "le", 8)],
     program.programId
   )[0];
   let treasury = anchor.web3.PublicKey.findProgramAddressSync(
@@ -53,6 +77,40 @@ describe("anchor_marketplace", () => {
     program.programId
   )[0];
 
+  let payment_mint_config = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("payment_mint"),
+      marketplace.toBuffer(),
+      payment_mint.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const payment_accounts = {
+    paymentMintConfig: payment_mint_config,
+    paymentPriceFeed: null,
+    paymentMint: payment_mint,
+    treasuryPaymentAccount: anchor.utils.token.associatedAddress({
+      mint: payment_mint,
+      owner: treasury,
+    }),
+    tokenProgram: TOKEN_PROGRAM_ID,
+  };
+
+  const vault_id = "VAULT-LDN-01";
+  const gold_vault_id = "VAULT-LDN-02";
+  let vault = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), marketplace.toBuffer(), Buffer.from(vault_id)],
+    program.programId
+  )[0];
+
+  // Awaits an instruction expected to fail and checks the program error it failed with
+  const expectFailure = async (rpc: Promise<string>, code: string) => {
+    const error = await rpc.then(() => null, (error) => error);
+    expect(error, `expected ${code}`).to.not.be.null;
+    expect(error.error.errorCode.code).to.equal(code);
+  };
+
   it.skip("Is initialized!", async () => {
     try {
       let name_of_program = "Gildore Marketplace";
@@ -60,8 +118,20 @@ describe("anchor_marketplace", () => {
       console.log(treasury);
       const tx = await program.methods
         .initialize({
+          id: marketplace_id,
           feeBps: 100,
+          // fee increases wait a day before applying
+          feeTimelock: new anchor.BN(24 * 60 * 60),
           name: name_of_program,
+          oracle: {
+            goldPriceFeed: gold_price_feed,
+            silverPriceFeed: silver_price_feed,
+            // the mock feeds carry a fixed publish time
+            maxPriceAge: new anchor.BN(10 * 365 * 24 * 60 * 60),
+            maxConfidenceBps: 100,
+          },
+          // holders the admin approved may resell
+          sellerPermission: { allowlisted: {} },
         })
         .accounts({
           admin: admin_wallet.publicKey,
@@ -80,6 +150,273 @@ describe("anchor_marketplace", () => {
     }
   });
 
+  it("should reject a marketplace name longer than 32 bytes", async () => {
+    // the id is never taken, the failed initialize leaves no account behind
+    await expectFailure(
+      program.methods
+        .initialize({
+          id: new anchor.BN(33),
+          feeBps: 100,
+          feeTimelock: new anchor.BN(0),
+          name: "G".repeat(33),
+          oracle: {
+            goldPriceFeed: gold_price_feed,
+            silverPriceFeed: silver_price_feed,
+            maxPriceAge: new anchor.BN(60),
+            maxConfidenceBps: 100,
+          },
+          sellerPermission: { open: {} },
+        })
+        .accounts({
+          admin: admin_wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin_wallet])
+        .rpc(),
+      "NameTooLong"
+    );
+  });
+
+  it("should update the marketplace config", async () => {
+    try {
+      const tx = await program.methods
+        .updateMarketplaceConfig({
+          name: "Gildore Marketplace",
+          // lowering or keeping the fee applies immediately
+          feeBps: 100,
+          feeTimelock: null,
+          sellerPermission: null,
+          // lamports paid out of an expired listing's rent to the cranker
+          expiryBounty: new anchor.BN(100_000),
+        })
+        .accounts({
+          marketplace,
+          admin: admin_wallet.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should hold fee changes behind the current timelock", async () => {
+    try {
+      await program.methods
+        .updateMarketplaceConfig({
+          name: null,
+          feeBps: 150,
+          feeTimelock: new anchor.BN(0),
+          sellerPermission: null,
+          expiryBounty: null,
+        })
+        .accounts({
+          marketplace,
+          admin: admin_wallet.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      const scheduled = await program.account.marketplace.fetch(marketplace);
+      expect(scheduled.feeBps).to.equal(100);
+      expect(scheduled.pendingFee.feeBps).to.equal(150);
+      expect(scheduled.feeTimelock.toNumber()).to.equal(24 * 60 * 60);
+      expect(scheduled.pendingFeeTimelock.feeTimelock.toNumber()).to.equal(0);
+
+      // keeping the fee and timelock drops both pending changes
+      const tx = await program.methods
+        .updateMarketplaceConfig({
+          name: null,
+          feeBps: 100,
+          feeTimelock: new anchor.BN(24 * 60 * 60),
+          sellerPermission: null,
+          expiryBounty: null,
+        })
+        .accounts({
+          marketplace,
+          admin: admin_wallet.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+
+      const restored = await program.account.marketplace.fetch(marketplace);
+      expect(restored.pendingFee).to.be.null;
+      expect(restored.pendingFeeTimelock).to.be.null;
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should approve a holder to resell on the marketplace", async () => {
+    try {
+      const tx = await program.methods
+        .setSellerApproval({ approved: true })
+        .accounts({
+          marketplace,
+          admin: admin_wallet.publicKey,
+          seller: user_1.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it.skip("should accept USDC as a payment mint", async () => {
+    try {
+      const tx = await program.methods
+        .addPaymentMint({
+          enabled: true,
+          priceFeed: null,
+        })
+        .accounts({
+          marketplace,
+          admin: admin_wallet.publicKey,
+          mint: payment_mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it.skip("should register the vault partner custodying the vaults", async () => {
+    try {
+      const tx = await program.methods
+        .registerVaultPartner({
+          authority: admin_wallet.publicKey,
+          encryptionKey: Array.from(vault_partner_encryption_key),
+          name: "London Bullion Vaults",
+          jurisdictionCode: "GB",
+        })
+        .accounts({
+          marketplace,
+          admin: admin_wallet.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it.skip("should register the vaults holding the metal", async () => {
+    try {
+      await program.methods
+        .createVault({
+          vaultId: gold_vault_id,
+          metal: { gold: {} },
+          // 10kg
+          totalMg: new anchor.BN(10_000_000),
+        })
+        .accounts({
+          marketplace,
+          admin: admin_wallet.publicKey,
+          // the admin wallet doubles as the vault partner in tests
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      const tx = await program.methods
+        .createVault({
+          vaultId: vault_id,
+          metal: { silver: {} },
+          // 1 tonne
+          totalMg: new anchor.BN(1_000_000_000),
+        })
+        .accounts({
+          marketplace,
+          admin: admin_wallet.publicKey,
+          // the admin wallet doubles as the vault partner in tests
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should set the vaults the vault partner custodies", async () => {
+    try {
+      const gold_vault = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vault"),
+          marketplace.toBuffer(),
+          Buffer.from(gold_vault_id),
+        ],
+        program.programId
+      )[0];
+      const vault_partner = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vault_partner"),
+          marketplace.toBuffer(),
+          admin_wallet.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+      const tx = await program.methods
+        .setPartnerVaults()
+        .accounts({
+          marketplace,
+          admin: admin_wallet.publicKey,
+          vaultPartner: vault_partner,
+        })
+        // every vault must belong to this marketplace
+        .remainingAccounts(
+          [vault, gold_vault].map((pubkey) => ({
+            pubkey,
+            isWritable: false,
+            isSigner: false,
+          }))
+        )
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
   it.skip("should fetch all marketplace accounts", async () => {
     try {
       let marketplace_accounts = await program.account.marketplace.all();
@@ -125,16 +462,29 @@ describe("anchor_marketplace", () => {
         creators: [admin_wallet.publicKey.toBase58()],
       };
       const metadataUri = await umi.uploader.uploadJson(metadata);
+      metadata_uri = metadataUri;
 
       const tx = await program.methods
         .createNft({
           name: "Silver Bar",
           uri: metadataUri,
+          attributes: {
+            metal: { silver: {} },
+            purity: 9999,
+            weightMg: new anchor.BN(125_000_000),
+            vaultId: vault_id,
+            serialNumber: "SLV-000001",
+          },
+          royalties: null,
         })
         .accounts({
+          marketplace,
           asset: asset.publicKey,
           collection: null,
+          collectionConfig: null,
           creator: admin_wallet.publicKey,
+          // the admin wallet doubles as the vault partner in tests
+          vaultPartnerAuthority: admin_wallet.publicKey,
         })
         // .remainingAccounts([
         //   {
@@ -156,6 +506,141 @@ describe("anchor_marketplace", () => {
     }
   });
 
+  it("should reject an Asset with invalid metal attributes", async () => {
+    const invalid_asset = anchor.web3.Keypair.generate();
+    await expectFailure(
+      program.methods
+        .createNft({
+          name: "Silver Bar",
+          uri: metadata_uri,
+          attributes: {
+            metal: { silver: {} },
+            // fineness is in parts per 10,000 and tops out at 9999 (999.9 fine)
+            purity: 10_000,
+            weightMg: new anchor.BN(125_000_000),
+            vaultId: vault_id,
+            serialNumber: "SLV-INVALID",
+          },
+          royalties: null,
+        })
+        .accounts({
+          marketplace,
+          asset: invalid_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          creator: admin_wallet.publicKey,
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([invalid_asset, admin_wallet])
+        .rpc(),
+      "InvalidPurity"
+    );
+  });
+
+  it("should refuse to mint more metal than the vault holds", async () => {
+    const oversized_asset = anchor.web3.Keypair.generate();
+    const { mintedMg } = await program.account.vault.fetch(vault);
+    await expectFailure(
+      program.methods
+        .createNft({
+          name: "Silver Bar",
+          uri: metadata_uri,
+          attributes: {
+            metal: { silver: {} },
+            purity: 9999,
+            // 2 tonnes, more than the 1 tonne the vault was registered with
+            weightMg: new anchor.BN(2_000_000_000),
+            vaultId: vault_id,
+            serialNumber: "SLV-OVERSIZED",
+          },
+          royalties: null,
+        })
+        .accounts({
+          marketplace,
+          asset: oversized_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          creator: admin_wallet.publicKey,
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([oversized_asset, admin_wallet])
+        .rpc(),
+      "InsufficientVaultInventory"
+    );
+
+    // the rejected mint leaves the vault inventory untouched
+    const after = await program.account.vault.fetch(vault);
+    expect(after.mintedMg.toString()).to.equal(mintedMg.toString());
+  });
+
+  it("should update Asset metadata through the marketplace", async () => {
+    try {
+      const tx = await program.methods
+        .modifyNft({
+          name: "Silver Bar 1kg",
+          // omitted fields keep their current value
+          uri: null,
+          attributes: null,
+        })
+        .accounts({
+          marketplace,
+          authority: admin_wallet.publicKey,
+          asset: asset.publicKey,
+          collection: null,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should create a gold product line collection", async () => {
+    try {
+      gold_collection = anchor.web3.Keypair.generate();
+      await program.methods
+        .createCollection({
+          name: "Gildore Gold 1oz",
+          uri: metadata_uri,
+          metal: { gold: {} },
+          tradable: false,
+        })
+        .accounts({
+          marketplace,
+          admin: admin_wallet.publicKey,
+          collection: gold_collection.publicKey,
+        })
+        .signers([gold_collection, admin_wallet])
+        .rpc();
+
+      const tx = await program.methods
+        .updateCollection({
+          name: null,
+          uri: null,
+          tradable: true,
+        })
+        .accounts({
+          marketplace,
+          admin: admin_wallet.publicKey,
+          collection: gold_collection.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
   it("should list Asset for sale in Marketplace", async () => {
     try {
       // asset = {
@@ -166,11 +651,15 @@ describe("anchor_marketplace", () => {
       const tx = await program.methods
         .listNft({
           tokenId: 20051,
-          price: new anchor.BN(500_000_000),
+          price: new anchor.BN(50_000_000),
+          kind: { fixed: {} },
+          expiresAt: null,
         })
         .accounts({
+          marketplace,
           asset: asset.publicKey,
           collection: null,
+          collectionConfig: null,
           seller: admin_wallet.publicKey,
         })
         .signers([admin_wallet])
@@ -185,6 +674,42 @@ describe("anchor_marketplace", () => {
     }
   });
 
+  it("should update the price of a listed Asset", async () => {
+    try {
+      const listing = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("listing"),
+          marketplace.toBuffer(),
+          asset.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+      const tx = await program.methods
+        .updateListing({
+          price: new anchor.BN(45_000_000),
+          tokenId: null,
+          kind: null,
+        })
+        .accounts({
+          marketplace,
+          seller: admin_wallet.publicKey,
+          // @ts-ignore
+          listing,
+          asset: asset.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
   it("should purchase Asset on sale in Marketplace", async () => {
     try {
       // asset = {
@@ -195,10 +720,21 @@ describe("anchor_marketplace", () => {
       const tx = await program.methods
         .purchaseNft()
         .accounts({
+          marketplace,
           asset: asset.publicKey,
           collection: null,
           seller: admin_wallet.publicKey,
           buyer: user_1.publicKey,
+          priceFeed: null,
+          ...payment_accounts,
+          buyerPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: user_1.publicKey,
+          }),
+          sellerPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: admin_wallet.publicKey,
+          }),
         })
         .signers([user_1])
         .rpc();
@@ -212,22 +748,1200 @@ describe("anchor_marketplace", () => {
     }
   });
 
-  it("should burn purchased Asset for physical redemption", async () => {
+  it("should request physical redemption of purchased Asset", async () => {
     try {
-      // asset = {
-      //   publicKey: new anchor.web3.PublicKey(
-      //     "EcCEUsUb8ERyKHWCCih1hfRdZZ5uxCo8r2m3Erq42a2g"
-      //   ),
-      // };
+      const redemption = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("redemption"),
+          marketplace.toBuffer(),
+          asset.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+      const shipping_details = JSON.stringify({
+        name: "Jane Saver",
+        address: "1 Bullion Street, London",
+      });
+      const salt = randomBytes(32);
+      const shipping_commitment = createHash("sha256")
+        .update(salt)
+        .update(shipping_details)
+        .digest();
+      // the client seals the details to the vault partner key off-chain, the program only stores the blob
+      const encrypted_shipping_details = randomBytes(48 + shipping_details.length);
+
+      const vault_partner = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vault_partner"),
+          marketplace.toBuffer(),
+          admin_wallet.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
       const tx = await program.methods
-        .redeemAsset()
+        .requestRedemption({
+          shippingCommitment: Array.from(shipping_commitment),
+          encryptedShippingDetails: encrypted_shipping_details,
+        })
         .accounts({
           asset: asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          owner: user_1.publicKey,
+          marketplace,
+          vault,
+          vaultPartner: vault_partner,
+          // the redemption fee is charged on the silver content at spot
+          priceFeed: silver_price_feed,
+          ...payment_accounts,
+          ownerPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: user_1.publicKey,
+          }),
+          escrowPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: redemption,
+          }),
+        })
+        .signers([user_1])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should accept the redemption and burn the Asset once shipped", async () => {
+    try {
+      const redemption = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("redemption"),
+          marketplace.toBuffer(),
+          asset.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+      await program.methods
+        .acceptRedemption()
+        .accounts({
+          vaultPartnerAuthority: admin_wallet.publicKey,
+          marketplace,
+          redemption,
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      const tx = await program.methods
+        .confirmShipment()
+        .accounts({
+          vaultPartnerAuthority: admin_wallet.publicKey,
+          marketplace,
+          redemption,
+          owner: user_1.publicKey,
+          asset: asset.publicKey,
+          collection: null,
+          vault,
+          paymentMint: payment_mint,
+          treasuryPaymentAccount: payment_accounts.treasuryPaymentAccount,
+          escrowPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: redemption,
+          }),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should let the holder cancel a redemption awaiting review", async () => {
+    try {
+      const redeemed_asset = anchor.web3.Keypair.generate();
+      await program.methods
+        .createNft({
+          name: "Silver Bar",
+          uri: metadata_uri,
+          attributes: {
+            metal: { silver: {} },
+            purity: 9999,
+            weightMg: new anchor.BN(31_103_477),
+            vaultId: vault_id,
+            serialNumber: "SLV-000007",
+          },
+          royalties: null,
+        })
+        .accounts({
+          marketplace,
+          asset: redeemed_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          creator: admin_wallet.publicKey,
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([redeemed_asset, admin_wallet])
+        .rpc();
+
+      const redemption = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("redemption"),
+          marketplace.toBuffer(),
+          redeemed_asset.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      const vault_partner = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vault_partner"),
+          marketplace.toBuffer(),
+          admin_wallet.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      const owner_payment_account = anchor.utils.token.associatedAddress({
+        mint: payment_mint,
+        owner: admin_wallet.publicKey,
+      });
+      const escrow_payment_account = anchor.utils.token.associatedAddress({
+        mint: payment_mint,
+        owner: redemption,
+      });
+
+      await program.methods
+        .requestRedemption({
+          shippingCommitment: Array.from(randomBytes(32)),
+          encryptedShippingDetails: randomBytes(96),
+        })
+        .accounts({
+          asset: redeemed_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          owner: admin_wallet.publicKey,
+          marketplace,
+          vault,
+          vaultPartner: vault_partner,
+          priceFeed: silver_price_feed,
+          ...payment_accounts,
+          ownerPaymentAccount: owner_payment_account,
+          escrowPaymentAccount: escrow_payment_account,
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      // the asset stays frozen for the redemption and cannot be relisted meanwhile
+      await expectFailure(
+        program.methods
+          .listNft({
+            tokenId: 20056,
+            price: new anchor.BN(50_000_000),
+            kind: { fixed: {} },
+            expiresAt: null,
+          })
+          .accounts({
+            marketplace,
+            asset: redeemed_asset.publicKey,
+            collection: null,
+            collectionConfig: null,
+            seller: admin_wallet.publicKey,
+          })
+          .signers([admin_wallet])
+          .rpc(),
+        "RedemptionPending"
+      );
+
+      const tx = await program.methods
+        .cancelRedemption()
+        .accounts({
+          owner: admin_wallet.publicKey,
+          marketplace,
+          redemption,
+          asset: redeemed_asset.publicKey,
+          collection: null,
+          paymentMint: payment_mint,
+          ownerPaymentAccount: owner_payment_account,
+          escrowPaymentAccount: escrow_payment_account,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+
+      const closed = await anchor
+        .getProvider()
+        .connection.getAccountInfo(redemption);
+      if (closed !== null) {
+        throw Error("redemption was not closed");
+      }
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should cancel a listing and return the Asset to the seller", async () => {
+    try {
+      const unsold_asset = anchor.web3.Keypair.generate();
+      await program.methods
+        .createNft({
+          name: "Silver Bar",
+          uri: metadata_uri,
+          attributes: {
+            metal: { silver: {} },
+            purity: 9999,
+            weightMg: new anchor.BN(125_000_000),
+            vaultId: vault_id,
+            serialNumber: "SLV-000002",
+          },
+          royalties: null,
+        })
+        .accounts({
+          marketplace,
+          asset: unsold_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          creator: admin_wallet.publicKey,
+          // the admin wallet doubles as the vault partner in tests
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([unsold_asset, admin_wallet])
+        .rpc();
+
+      await program.methods
+        .listNft({
+          tokenId: 20052,
+          price: new anchor.BN(50_000_000),
+          kind: { fixed: {} },
+          expiresAt: null,
+        })
+        .accounts({
+          marketplace,
+          asset: unsold_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          seller: admin_wallet.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      const tx = await program.methods
+        .cancelListing()
+        .accounts({
+          marketplace,
+          asset: unsold_asset.publicKey,
+          collection: null,
+          seller: admin_wallet.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should purchase a spot priced Asset using the gold price feed", async () => {
+    try {
+      spot_asset = anchor.web3.Keypair.generate();
+      await program.methods
+        .createNft({
+          name: "Gold Bar",
+          uri: metadata_uri,
+          attributes: {
+            metal: { gold: {} },
+            purity: 9999,
+            weightMg: new anchor.BN(1_000),
+            vaultId: gold_vault_id,
+            serialNumber: "GLD-000003",
+          },
+          // 5% to the minting wallet on every sale
+          royalties: {
+            basisPoints: 500,
+            creators: [{ address: admin_wallet.publicKey, percentage: 100 }],
+          },
+        })
+        .accounts({
+          marketplace,
+          asset: spot_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          creator: admin_wallet.publicKey,
+          // the admin wallet doubles as the vault partner in tests
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([spot_asset, admin_wallet])
+        .rpc();
+
+      await program.methods
+        .listNft({
+          tokenId: 30001,
+          price: new anchor.BN(0),
+          kind: {
+            // priced on the 1g of 999.9 gold in the asset's attributes
+            spot: { premiumBps: 200 },
+          },
+          expiresAt: null,
+        })
+        .accounts({
+          marketplace,
+          asset: spot_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          seller: admin_wallet.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      const spot_listing = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("listing"),
+          marketplace.toBuffer(),
+          spot_asset.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+      // a spot listing has no fixed price to update
+      await expectFailure(
+        program.methods
+          .updateListing({
+            price: new anchor.BN(45_000_000),
+            tokenId: null,
+            kind: null,
+          })
+          .accounts({
+            marketplace,
+            seller: admin_wallet.publicKey,
+            // @ts-ignore
+            listing: spot_listing,
+            asset: spot_asset.publicKey,
+          })
+          .signers([admin_wallet])
+          .rpc(),
+        "PriceNotFixed"
+      );
+
+      // the seller raises their premium over spot instead
+      await program.methods
+        .updateListing({
+          price: null,
+          tokenId: null,
+          kind: { spot: { premiumBps: 300 } },
+        })
+        .accounts({
+          marketplace,
+          seller: admin_wallet.publicKey,
+          // @ts-ignore
+          listing: spot_listing,
+          asset: spot_asset.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      const updated = await program.account.listing.fetch(spot_listing);
+      expect(updated.kind.spot.premiumBps).to.equal(300);
+      expect(updated.kind.spot.weightMg.toNumber()).to.equal(1_000);
+
+      const tx = await program.methods
+        .purchaseNft()
+        .accounts({
+          marketplace,
+          asset: spot_asset.publicKey,
+          collection: null,
+          seller: admin_wallet.publicKey,
+          buyer: user_1.publicKey,
+          priceFeed: gold_price_feed,
+          ...payment_accounts,
+          buyerPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: user_1.publicKey,
+          }),
+          sellerPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: admin_wallet.publicKey,
+          }),
+        })
+        // creator payment accounts in royalties plugin order
+        .remainingAccounts([
+          {
+            pubkey: anchor.utils.token.associatedAddress({
+              mint: payment_mint,
+              owner: admin_wallet.publicKey,
+            }),
+            isWritable: true,
+            isSigner: false,
+          },
+        ])
+        .signers([user_1])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should let the buyer relist the Asset they bought", async () => {
+    try {
+      const tx = await program.methods
+        .listNft({
+          tokenId: 30002,
+          price: new anchor.BN(80_000_000),
+          kind: { fixed: {} },
+          expiresAt: null,
+        })
+        .accounts({
+          marketplace,
+          asset: spot_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          seller: user_1.publicKey,
+        })
+        .signers([user_1])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should sell a Dutch auction Asset at its declined price", async () => {
+    try {
+      const dutch_asset = anchor.web3.Keypair.generate();
+      await program.methods
+        .createNft({
+          name: "Silver Bar",
+          uri: metadata_uri,
+          attributes: {
+            metal: { silver: {} },
+            purity: 9999,
+            weightMg: new anchor.BN(125_000_000),
+            vaultId: vault_id,
+            serialNumber: "SLV-000004",
+          },
+          royalties: null,
+        })
+        .accounts({
+          marketplace,
+          asset: dutch_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          creator: admin_wallet.publicKey,
+          // the admin wallet doubles as the vault partner in tests
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([dutch_asset, admin_wallet])
+        .rpc();
+
+      // started 5 minutes into a 10 minute decline, so it should sell for about 50 USDC
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .listNft({
+          tokenId: 20054,
+          price: new anchor.BN(0),
+          kind: {
+            dutch: {
+              startPrice: new anchor.BN(60_000_000),
+              floorPrice: new anchor.BN(40_000_000),
+              startTime: new anchor.BN(now - 300),
+              endTime: new anchor.BN(now + 300),
+            },
+          },
+          expiresAt: null,
+        })
+        .accounts({
+          marketplace,
+          asset: dutch_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          seller: admin_wallet.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      const tx = await program.methods
+        .purchaseNft()
+        .accounts({
+          marketplace,
+          asset: dutch_asset.publicKey,
+          collection: null,
+          seller: admin_wallet.publicKey,
+          buyer: user_1.publicKey,
+          priceFeed: null,
+          ...payment_accounts,
+          buyerPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: user_1.publicKey,
+          }),
+          sellerPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: admin_wallet.publicKey,
+          }),
+        })
+        .signers([user_1])
+        .rpc();
+      console.log("Your transaction signature", tx);
+
+      const sale_record = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("sale_record"),
+          marketplace.toBuffer(),
+          dutch_asset.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      const sale = await program.account.saleRecord.fetch(sale_record);
+      console.log("Sold for", sale.price.toString());
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should auction an Asset and settle it to the highest bidder", async () => {
+    try {
+      const auction_asset = anchor.web3.Keypair.generate();
+      await program.methods
+        .createNft({
+          name: "Silver Bar",
+          uri: metadata_uri,
+          attributes: {
+            metal: { silver: {} },
+            purity: 9999,
+            weightMg: new anchor.BN(125_000_000),
+            vaultId: vault_id,
+            serialNumber: "SLV-000003",
+          },
+          royalties: null,
+        })
+        .accounts({
+          marketplace,
+          asset: auction_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          creator: admin_wallet.publicKey,
+          // the admin wallet doubles as the vault partner in tests
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([auction_asset, admin_wallet])
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .listNft({
+          tokenId: 20053,
+          price: new anchor.BN(0),
+          kind: {
+            auction: {
+              paymentMint: payment_mint,
+              reservePrice: new anchor.BN(10_000_000),
+              minBidIncrement: new anchor.BN(1_000_000),
+              startTime: new anchor.BN(now - 60),
+              endTime: new anchor.BN(now + 15),
+              extensionSecs: 0,
+            },
+          },
+          expiresAt: null,
+        })
+        .accounts({
+          marketplace,
+          asset: auction_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          seller: admin_wallet.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      const listing = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("listing"),
+          marketplace.toBuffer(),
+          auction_asset.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      const bid = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("bid"), listing.toBuffer(), user_1.publicKey.toBuffer()],
+        program.programId
+      )[0];
+      const bid_payment_account = anchor.utils.token.associatedAddress({
+        mint: payment_mint,
+        owner: bid,
+      });
+
+      await program.methods
+        .placeBid(new anchor.BN(12_000_000))
+        .accounts({
+          marketplace,
+          bidder: user_1.publicKey,
+          // @ts-ignore
+          listing,
+          previousBidder: null,
+          previousBid: null,
+          paymentMintConfig: payment_mint_config,
+          paymentMint: payment_mint,
+          bidderPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: user_1.publicKey,
+          }),
+          bidPaymentAccount: bid_payment_account,
+          previousBidderPaymentAccount: null,
+          previousBidPaymentAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user_1])
+        .rpc();
+
+      // the highest bidder raises their own bid, escrowing only the difference
+      await program.methods
+        .placeBid(new anchor.BN(13_000_000))
+        .accounts({
+          marketplace,
+          bidder: user_1.publicKey,
+          // @ts-ignore
+          listing,
+          previousBidder: null,
+          previousBid: null,
+          paymentMintConfig: payment_mint_config,
+          paymentMint: payment_mint,
+          bidderPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: user_1.publicKey,
+          }),
+          bidPaymentAccount: bid_payment_account,
+          previousBidderPaymentAccount: null,
+          previousBidPaymentAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user_1])
+        .rpc();
+
+      const raised_bid = await program.account.bid.fetch(bid);
+      expect(raised_bid.amount.toNumber()).to.equal(13_000_000);
+      const escrowed = await anchor
+        .getProvider()
+        .connection.getTokenAccountBalance(bid_payment_account);
+      expect(escrowed.value.amount).to.equal("13000000");
+
+      // wait out the auction before settling
+      await new Promise((resolve) => setTimeout(resolve, 16_000));
+
+      const tx = await program.methods
+        .settleAuction()
+        .accounts({
+          marketplace,
+          payer: admin_wallet.publicKey,
+          seller: admin_wallet.publicKey,
+          winner: user_1.publicKey,
+          asset: auction_asset.publicKey,
+          collection: null,
+          // @ts-ignore
+          bid,
+          ...payment_accounts,
+          bidPaymentAccount: bid_payment_account,
+          sellerPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: admin_wallet.publicKey,
+          }),
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should make, cancel and accept offers on an unlisted Asset", async () => {
+    try {
+      const offered_asset = anchor.web3.Keypair.generate();
+      await program.methods
+        .createNft({
+          name: "Silver Bar",
+          uri: metadata_uri,
+          attributes: {
+            metal: { silver: {} },
+            purity: 9999,
+            weightMg: new anchor.BN(125_000_000),
+            vaultId: vault_id,
+            serialNumber: "SLV-000005",
+          },
+          royalties: null,
+        })
+        .accounts({
+          marketplace,
+          asset: offered_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          creator: admin_wallet.publicKey,
+          // the admin wallet doubles as the vault partner in tests
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([offered_asset, admin_wallet])
+        .rpc();
+
+      const offer = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("offer"),
+          marketplace.toBuffer(),
+          offered_asset.publicKey.toBuffer(),
+          user_1.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      const offer_accounts = {
+        marketplace,
+        buyer: user_1.publicKey,
+        paymentMint: payment_mint,
+        buyerPaymentAccount: anchor.utils.token.associatedAddress({
+          mint: payment_mint,
           owner: user_1.publicKey,
+        }),
+        escrowPaymentAccount: anchor.utils.token.associatedAddress({
+          mint: payment_mint,
+          owner: offer,
+        }),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      };
+      const make_offer = (amount: number) =>
+        program.methods
+          .makeOffer({
+            amount: new anchor.BN(amount),
+            expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+          })
+          .accounts({
+            ...offer_accounts,
+            asset: offered_asset.publicKey,
+            paymentMintConfig: payment_mint_config,
+          })
+          .signers([user_1])
+          .rpc();
+
+      await make_offer(30_000_000);
+      await program.methods
+        .cancelOffer()
+        .accounts({
+          ...offer_accounts,
+          // @ts-ignore
+          offer,
+        })
+        .signers([user_1])
+        .rpc();
+
+      await make_offer(35_000_000);
+      const tx = await program.methods
+        .acceptOffer()
+        .accounts({
+          marketplace,
           seller: admin_wallet.publicKey,
+          buyer: user_1.publicKey,
+          asset: offered_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          listing: null,
+          escrow: null,
+          ...payment_accounts,
+          escrowPaymentAccount: offer_accounts.escrowPaymentAccount,
+          sellerPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: admin_wallet.publicKey,
+          }),
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should fill a gold collection offer with a matching Asset", async () => {
+    try {
+      const offer_id = new anchor.BN(1);
+      const collection_offer = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("collection_offer"),
+          marketplace.toBuffer(),
+          user_1.publicKey.toBuffer(),
+          offer_id.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+      const collection_config = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("collection"),
+          marketplace.toBuffer(),
+          gold_collection.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      const escrow_payment_account = anchor.utils.token.associatedAddress({
+        mint: payment_mint,
+        owner: collection_offer,
+      });
+      const buyer_payment_account = anchor.utils.token.associatedAddress({
+        mint: payment_mint,
+        owner: user_1.publicKey,
+      });
+
+      // any two bars of at least 1g of 999.0 gold
+      await program.methods
+        .makeCollectionOffer({
+          id: offer_id,
+          metal: { gold: {} },
+          minPurity: 9990,
+          minWeightMg: new anchor.BN(1_000),
+          price: new anchor.BN(70_000_000),
+          quantity: 2,
+          expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+        })
+        .accounts({
+          marketplace,
+          buyer: user_1.publicKey,
+          collectionConfig: collection_config,
+          paymentMintConfig: payment_mint_config,
+          paymentMint: payment_mint,
+          buyerPaymentAccount: buyer_payment_account,
+          escrowPaymentAccount: escrow_payment_account,
+          tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([user_1])
         .rpc();
+
+      const gold_asset = anchor.web3.Keypair.generate();
+      await program.methods
+        .createNft({
+          name: "Gold Bar",
+          uri: metadata_uri,
+          attributes: {
+            metal: { gold: {} },
+            purity: 9999,
+            weightMg: new anchor.BN(1_000),
+            vaultId: gold_vault_id,
+            serialNumber: "GLD-000004",
+          },
+          royalties: null,
+        })
+        .accounts({
+          marketplace,
+          asset: gold_asset.publicKey,
+          collection: gold_collection.publicKey,
+          collectionConfig: collection_config,
+          creator: admin_wallet.publicKey,
+          // the admin wallet doubles as the vault partner in tests
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([gold_asset, admin_wallet])
+        .rpc();
+
+      await program.methods
+        .fillCollectionOffer()
+        .accounts({
+          marketplace,
+          seller: admin_wallet.publicKey,
+          buyer: user_1.publicKey,
+          asset: gold_asset.publicKey,
+          collection: gold_collection.publicKey,
+          // @ts-ignore
+          collectionOffer: collection_offer,
+          ...payment_accounts,
+          escrowPaymentAccount: escrow_payment_account,
+          sellerPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: admin_wallet.publicKey,
+          }),
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      // refund the unit nobody filled
+      const tx = await program.methods
+        .cancelCollectionOffer()
+        .accounts({
+          marketplace,
+          buyer: user_1.publicKey,
+          // @ts-ignore
+          collectionOffer: collection_offer,
+          paymentMint: payment_mint,
+          buyerPaymentAccount: buyer_payment_account,
+          escrowPaymentAccount: escrow_payment_account,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user_1])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should redeem a gold Asset minted into a collection", async () => {
+    try {
+      const collection_config = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("collection"),
+          marketplace.toBuffer(),
+          gold_collection.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      const gold_vault = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vault"),
+          marketplace.toBuffer(),
+          Buffer.from(gold_vault_id),
+        ],
+        program.programId
+      )[0];
+      const vault_partner = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vault_partner"),
+          marketplace.toBuffer(),
+          admin_wallet.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+      const gold_asset = anchor.web3.Keypair.generate();
+      await program.methods
+        .createNft({
+          name: "Gold Bar",
+          uri: metadata_uri,
+          attributes: {
+            metal: { gold: {} },
+            purity: 9999,
+            weightMg: new anchor.BN(1_000),
+            vaultId: gold_vault_id,
+            serialNumber: "GLD-000005",
+          },
+          royalties: null,
+        })
+        .accounts({
+          marketplace,
+          asset: gold_asset.publicKey,
+          collection: gold_collection.publicKey,
+          collectionConfig: collection_config,
+          creator: admin_wallet.publicKey,
+          // the admin wallet doubles as the vault partner in tests
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([gold_asset, admin_wallet])
+        .rpc();
+
+      const redemption = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("redemption"),
+          marketplace.toBuffer(),
+          gold_asset.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      const escrow_payment_account = anchor.utils.token.associatedAddress({
+        mint: payment_mint,
+        owner: redemption,
+      });
+
+      await program.methods
+        .requestRedemption({
+          shippingCommitment: Array.from(randomBytes(32)),
+          encryptedShippingDetails: randomBytes(96),
+        })
+        .accounts({
+          asset: gold_asset.publicKey,
+          collection: gold_collection.publicKey,
+          collectionConfig: collection_config,
+          owner: admin_wallet.publicKey,
+          marketplace,
+          vault: gold_vault,
+          vaultPartner: vault_partner,
+          priceFeed: gold_price_feed,
+          ...payment_accounts,
+          ownerPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: admin_wallet.publicKey,
+          }),
+          escrowPaymentAccount: escrow_payment_account,
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      await program.methods
+        .acceptRedemption()
+        .accounts({
+          vaultPartnerAuthority: admin_wallet.publicKey,
+          marketplace,
+          redemption,
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      const tx = await program.methods
+        .confirmShipment()
+        .accounts({
+          vaultPartnerAuthority: admin_wallet.publicKey,
+          marketplace,
+          redemption,
+          owner: admin_wallet.publicKey,
+          asset: gold_asset.publicKey,
+          collection: gold_collection.publicKey,
+          vault: gold_vault,
+          paymentMint: payment_mint,
+          treasuryPaymentAccount: payment_accounts.treasuryPaymentAccount,
+          escrowPaymentAccount: escrow_payment_account,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin_wallet])
+        .rpc();
+      console.log("Your transaction signature", tx);
+
+      const burned = await anchor
+        .getProvider()
+        .connection.getAccountInfo(gold_asset.publicKey);
+      if (burned !== null && burned.data.length > 1) {
+        throw Error("asset was not burned");
+      }
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should let anyone return an expired listing to the seller", async () => {
+    try {
+      const expiring_asset = anchor.web3.Keypair.generate();
+      await program.methods
+        .createNft({
+          name: "Silver Bar",
+          uri: metadata_uri,
+          attributes: {
+            metal: { silver: {} },
+            purity: 9999,
+            weightMg: new anchor.BN(125_000_000),
+            vaultId: vault_id,
+            serialNumber: "SLV-000006",
+          },
+          royalties: null,
+        })
+        .accounts({
+          marketplace,
+          asset: expiring_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          creator: admin_wallet.publicKey,
+          // the admin wallet doubles as the vault partner in tests
+          vaultPartnerAuthority: admin_wallet.publicKey,
+        })
+        .signers([expiring_asset, admin_wallet])
+        .rpc();
+
+      await program.methods
+        .listNft({
+          tokenId: 20055,
+          price: new anchor.BN(50_000_000),
+          kind: { fixed: {} },
+          expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 5),
+        })
+        .accounts({
+          marketplace,
+          asset: expiring_asset.publicKey,
+          collection: null,
+          collectionConfig: null,
+          seller: admin_wallet.publicKey,
+        })
+        .signers([admin_wallet])
+        .rpc();
+
+      // wait for the listing to expire
+      await new Promise((resolve) => setTimeout(resolve, 6_000));
+
+      const tx = await program.methods
+        .expireListing()
+        .accounts({
+          marketplace,
+          cranker: user_1.publicKey,
+          seller: admin_wallet.publicKey,
+          asset: expiring_asset.publicKey,
+          collection: null,
+        })
+        .signers([user_1])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should withdraw collected USDC fees from the treasury", async () => {
+    try {
+      const { value } = await program.provider.connection.getTokenAccountBalance(
+        payment_accounts.treasuryPaymentAccount
+      );
+
+      const tx = await program.methods
+        .withdrawTreasury(new anchor.BN(value.amount))
+        .accounts({
+          marketplace,
+          admin: admin_wallet.publicKey,
+          destination: admin_wallet.publicKey,
+          mint: payment_mint,
+          treasuryPaymentAccount: payment_accounts.treasuryPaymentAccount,
+          destinationPaymentAccount: anchor.utils.token.associatedAddress({
+            mint: payment_mint,
+            owner: admin_wallet.publicKey,
+          }),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin_wallet])
+        .rpc();
       console.log("Your transaction signature", tx);
     } catch (error) {
       console.log(error);
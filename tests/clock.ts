@@ -0,0 +1,409 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import { BankrunProvider } from "anchor-bankrun";
+import { Clock, ProgramTestContext, start } from "solana-bankrun";
+import { expect } from "chai";
+
+import { AnchorMarketplace } from "../target/types/anchor_marketplace";
+import IDL from "../target/idl/anchor_marketplace.json";
+
+// Time dependent behaviour runs against an in-process bank whose clock can be warped.
+// mpl-core is loaded from tests/fixtures/mpl_core.so, see the `fixtures` script in package.json
+const MPL_CORE_PROGRAM_ID = new anchor.web3.PublicKey(
+  "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d"
+);
+const NATIVE_MINT = new anchor.web3.PublicKey(
+  "So11111111111111111111111111111111111111112"
+);
+const PRICE_FEED_OWNER = new anchor.web3.PublicKey(
+  "gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s"
+);
+const LAMPORTS = 1_000 * anchor.web3.LAMPORTS_PER_SOL;
+
+describe("anchor_marketplace clock", () => {
+  const admin = anchor.web3.Keypair.generate();
+  const buyer = anchor.web3.Keypair.generate();
+
+  // only the silver feed is loaded, written by `setPriceFeed` before each spot purchase
+  const gold_price_feed = anchor.web3.Keypair.generate().publicKey;
+  const silver_price_feed = anchor.web3.Keypair.generate().publicKey;
+
+  const marketplace_id = new anchor.BN(7);
+  const vault_id = "VAULT-CLK-01";
+
+  let context: ProgramTestContext;
+  let program: Program<AnchorMarketplace>;
+  let marketplace: anchor.web3.PublicKey;
+  let payment_mint_config: anchor.web3.PublicKey;
+  let serial = 0;
+
+  // the bank starts with a wrapped SOL mint so native SOL can be registered as a payment mint
+  const native_mint_data = Buffer.alloc(82);
+  native_mint_data.writeUInt8(9, 44);
+  native_mint_data.writeUInt8(1, 45);
+
+  const now = async () =>
+    Number((await context.banksClient.getClock()).unixTimestamp);
+
+  const warpTo = async (unix_timestamp: number) => {
+    const clock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        BigInt(unix_timestamp)
+      )
+    );
+  };
+
+  // Pyth-style price account quoting silver per troy ounce at expo -8, see state/price_feed.rs
+  const setPriceFeed = (conf: number, publish_time: number) => {
+    const data = Buffer.alloc(240);
+    data.writeUInt32LE(0xa1b2c3d4, 0);
+    data.writeUInt32LE(2, 4);
+    data.writeUInt32LE(3, 8);
+    data.writeInt32LE(-8, 20);
+    data.writeBigInt64LE(BigInt(publish_time), 96);
+    // 30 USD
+    data.writeBigInt64LE(BigInt(3_000_000_000), 208);
+    data.writeBigUInt64LE(BigInt(conf), 216);
+    data.writeUInt32LE(1, 224);
+    context.setAccount(silver_price_feed, {
+      lamports: anchor.web3.LAMPORTS_PER_SOL,
+      data,
+      owner: PRICE_FEED_OWNER,
+      executable: false,
+    });
+  };
+
+  // Sends a transaction expected to fail and checks the program error it failed with
+  const expectFailure = async (
+    transaction: anchor.web3.Transaction,
+    signers: anchor.web3.Keypair[],
+    code: string
+  ) => {
+    transaction.recentBlockhash = context.lastBlockhash;
+    transaction.feePayer = signers[0].publicKey;
+    transaction.sign(...signers);
+    const { result, meta } = await context.banksClient.tryProcessTransaction(
+      transaction
+    );
+    expect(result).to.not.be.null;
+    expect(meta.logMessages.join("\n")).to.include(`Error Code: ${code}.`);
+  };
+
+  const mintSilverBar = async () => {
+    const asset = anchor.web3.Keypair.generate();
+    serial += 1;
+    await program.methods
+      .createNft({
+        name: "Silver Bar",
+        uri: "https://example.com/silver-bar.json",
+        attributes: {
+          metal: { silver: {} },
+          purity: 9999,
+          weightMg: new anchor.BN(31_103_477),
+          vaultId: vault_id,
+          serialNumber: `CLK-${serial.toString().padStart(6, "0")}`,
+        },
+        royalties: null,
+      })
+      .accounts({
+        marketplace,
+        asset: asset.publicKey,
+        collection: null,
+        collectionConfig: null,
+        creator: admin.publicKey,
+        vaultPartnerAuthority: admin.publicKey,
+      })
+      .signers([asset, admin])
+      .rpc();
+    return asset.publicKey;
+  };
+
+  const purchase = (
+    asset: anchor.web3.PublicKey,
+    price_feed: anchor.web3.PublicKey | null = null
+  ) =>
+    program.methods.purchaseNft().accounts({
+      marketplace,
+      asset,
+      collection: null,
+      seller: admin.publicKey,
+      buyer: buyer.publicKey,
+      priceFeed: price_feed,
+      paymentMintConfig: payment_mint_config,
+      paymentPriceFeed: null,
+      paymentMint: null,
+      buyerPaymentAccount: null,
+      sellerPaymentAccount: null,
+      treasuryPaymentAccount: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+  const salePrice = async (asset: anchor.web3.PublicKey) => {
+    const sale_record = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("sale_record"), marketplace.toBuffer(), asset.toBuffer()],
+      program.programId
+    )[0];
+    const sale = await program.account.saleRecord.fetch(sale_record);
+    return sale.price.toNumber();
+  };
+
+  before(async () => {
+    process.env.SBF_OUT_DIR = "target/deploy";
+    const program_id = new anchor.web3.PublicKey(IDL.address);
+
+    context = await start(
+      [
+        { name: "anchor_marketplace", programId: program_id },
+        { name: "mpl_core", programId: MPL_CORE_PROGRAM_ID },
+      ],
+      [
+        ...[admin, buyer].map((wallet) => ({
+          address: wallet.publicKey,
+          info: {
+            lamports: LAMPORTS,
+            data: Buffer.alloc(0),
+            owner: anchor.web3.SystemProgram.programId,
+            executable: false,
+          },
+        })),
+        {
+          address: NATIVE_MINT,
+          info: {
+            lamports: anchor.web3.LAMPORTS_PER_SOL,
+            data: native_mint_data,
+            owner: TOKEN_PROGRAM_ID,
+            executable: false,
+          },
+        },
+      ]
+    );
+    const provider = new BankrunProvider(context);
+    program = new Program<AnchorMarketplace>(
+      IDL as AnchorMarketplace,
+      provider
+    );
+
+    marketplace = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("marketplace"), marketplace_id.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+    payment_mint_config = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("payment_mint"),
+        marketplace.toBuffer(),
+        NATIVE_MINT.toBuffer(),
+      ],
+      program.programId
+    )[0];
+
+    await program.methods
+      .initialize({
+        id: marketplace_id,
+        name: "Clock Marketplace",
+        feeBps: 100,
+        feeTimelock: new anchor.BN(0),
+        oracle: {
+          goldPriceFeed: gold_price_feed,
+          silverPriceFeed: silver_price_feed,
+          maxPriceAge: new anchor.BN(60),
+          maxConfidenceBps: 100,
+        },
+        sellerPermission: { open: {} },
+      })
+      .accounts({
+        admin: admin.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    // without a price feed SOL is taken 1:1 with USD, which keeps expected amounts readable
+    await program.methods
+      .addPaymentMint({ enabled: true, priceFeed: null })
+      .accounts({
+        marketplace,
+        admin: admin.publicKey,
+        mint: NATIVE_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .registerVaultPartner({
+        authority: admin.publicKey,
+        encryptionKey: Array.from(Buffer.alloc(32, 1)),
+        name: "Clock Vaults",
+        jurisdictionCode: "GB",
+      })
+      .accounts({
+        marketplace,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .createVault({
+        vaultId: vault_id,
+        metal: { silver: {} },
+        totalMg: new anchor.BN(1_000_000_000),
+      })
+      .accounts({
+        marketplace,
+        admin: admin.publicKey,
+        vaultPartnerAuthority: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  it("should decline a Dutch auction price from start to floor", async () => {
+    try {
+      // a 10 minute decline from 60 to 40 USD
+      const start_time = (await now()) + 60;
+      const end_time = start_time + 600;
+
+      const assets = [];
+      for (let token_id = 1; token_id <= 3; token_id++) {
+        const asset = await mintSilverBar();
+        await program.methods
+          .listNft({
+            tokenId: token_id,
+            price: new anchor.BN(0),
+            kind: {
+              dutch: {
+                startPrice: new anchor.BN(60_000_000),
+                floorPrice: new anchor.BN(40_000_000),
+                startTime: new anchor.BN(start_time),
+                endTime: new anchor.BN(end_time),
+              },
+            },
+            expiresAt: null,
+          })
+          .accounts({
+            marketplace,
+            asset,
+            collection: null,
+            collectionConfig: null,
+            seller: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+        assets.push(asset);
+      }
+
+      const [at_start, at_midpoint, at_floor] = assets;
+
+      await warpTo(start_time);
+      await purchase(at_start).signers([buyer]).rpc();
+      expect(await salePrice(at_start)).to.equal(60_000_000);
+
+      await warpTo(start_time + 300);
+      await purchase(at_midpoint).signers([buyer]).rpc();
+      expect(await salePrice(at_midpoint)).to.equal(50_000_000);
+
+      // the price stays at the floor once the decline has ended
+      await warpTo(end_time + 3_600);
+      await purchase(at_floor).signers([buyer]).rpc();
+      expect(await salePrice(at_floor)).to.equal(40_000_000);
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should refuse spot purchases on a stale or uncertain price", async () => {
+    try {
+      const assets = [];
+      for (let token_id = 4; token_id <= 5; token_id++) {
+        const asset = await mintSilverBar();
+        await program.methods
+          .listNft({
+            tokenId: token_id,
+            price: new anchor.BN(0),
+            kind: { spot: { premiumBps: 0 } },
+            expiresAt: null,
+          })
+          .accounts({
+            marketplace,
+            asset,
+            collection: null,
+            collectionConfig: null,
+            seller: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc();
+        assets.push(asset);
+      }
+      const [stale_asset, uncertain_asset] = assets;
+
+      // published an hour ago, past the 60 second maximum age
+      setPriceFeed(1_000_000, (await now()) - 3_600);
+      await expectFailure(
+        await purchase(stale_asset, silver_price_feed).transaction(),
+        [buyer],
+        "StalePrice"
+      );
+
+      // a 2% confidence interval, wider than the 1% maximum
+      setPriceFeed(60_000_000, await now());
+      await expectFailure(
+        await purchase(uncertain_asset, silver_price_feed).transaction(),
+        [buyer],
+        "PriceConfidenceTooWide"
+      );
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+
+  it("should refuse to sell an expired listing", async () => {
+    try {
+      const asset = await mintSilverBar();
+      const expires_at = (await now()) + 100;
+      await program.methods
+        .listNft({
+          tokenId: 6,
+          price: new anchor.BN(50_000_000),
+          kind: { fixed: {} },
+          expiresAt: new anchor.BN(expires_at),
+        })
+        .accounts({
+          marketplace,
+          asset,
+          collection: null,
+          collectionConfig: null,
+          seller: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+
+      await warpTo(expires_at);
+      await expectFailure(
+        await purchase(asset).transaction(),
+        [buyer],
+        "ListingExpired"
+      );
+    } catch (error) {
+      console.log(error);
+      if (error.logs) {
+        console.log(error.logs);
+      }
+      throw Error("error occured");
+    }
+  });
+});